@@ -1,15 +1,26 @@
 use crate::error::ContractError;
 use crate::events;
-use crate::storage::DataKey;
-use crate::types::{CollectionConfig, RoyaltyInfo, TokenMetadata};
-use soroban_sdk::{Address, Env, String, Vec, contract, contractimpl, panic_with_error};
+use crate::storage::{
+    DataKey, MAX_DERIVED_URI_LEN, MAX_TOKEN_BATCH, MAX_WHITELIST_BATCH, read, write,
+};
+use crate::types::{CollectionConfig, MintMode, MintPrice, RoyaltyInfo, TokenMetadata};
+use soroban_sdk::{
+    Address, BytesN, Env, Executable, IntoVal, InvokeError, String, Symbol, Vec, contract,
+    contractimpl, panic_with_error, token, vec,
+};
 
 #[contract]
 pub struct NftCollection;
 
 #[contractimpl]
 impl NftCollection {
-    pub fn init(env: Env, admin: Address, factory_address: Address, config: CollectionConfig) {
+    pub fn init(
+        env: Env,
+        admin: Address,
+        factory_address: Address,
+        creator: Address,
+        config: CollectionConfig,
+    ) {
         if env.storage().instance().has(&DataKey::CollectionConfig) {
             panic_with_error!(&env, ContractError::AlreadyInitialized);
         }
@@ -36,6 +47,13 @@ impl NftCollection {
             .set(&DataKey::CollectionConfig, &config);
         env.storage().instance().set(&DataKey::TotalSupply, &0u32);
         env.storage().instance().set(&DataKey::IsPaused, &false);
+        env.storage().instance().set(&DataKey::Creator, &creator);
+        let mode = if config.is_public_mint {
+            MintMode::Public
+        } else {
+            MintMode::MinterOnly
+        };
+        env.storage().instance().set(&DataKey::MintMode, &mode);
 
         let royalty_info = RoyaltyInfo {
             recipient: config.royalty_recipient.clone(),
@@ -49,6 +67,8 @@ impl NftCollection {
         env.storage().instance().set(&DataKey::Minter(admin), &true);
     }
 
+    /// Mints `token_id` with an explicit URI. Designated minters only; others
+    /// go through `mint_next`.
     pub fn mint(
         env: Env,
         minter: Address,
@@ -58,16 +78,36 @@ impl NftCollection {
         attributes: Vec<(String, String)>,
     ) -> Result<(), ContractError> {
         minter.require_auth();
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::FactoryAdmin)
-            .unwrap();
-        // Allow only admin or designated minters (the caller)
         if !Self::is_minter(&env, &minter) {
-            panic_with_error!(&env, ContractError::NotMinter);
+            return Err(ContractError::NotMinter);
         }
+        Self::mint_token(&env, &minter, None, to, token_id, uri, attributes)
+    }
+
+    /// Mints the next free ID to `to`, with `base_uri` followed by the ID as
+    /// its URI. The path for whitelist and public minters, who may not pick
+    /// IDs or URIs. Returns the minted ID.
+    pub fn mint_next(env: Env, minter: Address, to: Address) -> Result<u32, ContractError> {
+        minter.require_auth();
+        let mode = Self::authorize_minter(&env, &minter);
+        let token_id = Self::next_token_id(&env)?;
+        let uri = Self::derived_uri(&env, token_id)?;
+        Self::mint_token(&env, &minter, mode, to, token_id, uri, Vec::new(&env))?;
+        Ok(token_id)
+    }
 
+    fn mint_token(
+        env: &Env,
+        minter: &Address,
+        mode: Option<MintMode>,
+        to: Address,
+        token_id: u32,
+        uri: String,
+        attributes: Vec<(String, String)>,
+    ) -> Result<(), ContractError> {
+        if Self::is_semi_fungible(env.clone()) {
+            return Err(ContractError::WrongTokenMode);
+        }
         if env
             .storage()
             .instance()
@@ -76,6 +116,90 @@ impl NftCollection {
             return Err(ContractError::TokenAlreadyExists);
         }
 
+        Self::reserve_supply(env, 1)?;
+        Self::charge_mint(env, minter, mode, 1)?;
+
+        Self::save_metadata(env, token_id, uri, attributes);
+        env.storage().instance().set(&DataKey::Owner(token_id), &to);
+
+        let balance = Self::balance_of(env.clone(), to.clone(), token_id);
+        Self::set_balance(env, &to, token_id, balance + 1);
+
+        events::emit_mint(env, env.current_contract_address(), to, token_id, 1);
+        Self::report_to_factory(env, 1, 0);
+
+        Ok(())
+    }
+
+    /// Takes the next ID from the collection counter, stepping over IDs that
+    /// minters already chose explicitly.
+    fn next_token_id(env: &Env) -> Result<u32, ContractError> {
+        let mut token_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .unwrap_or(1);
+        while env
+            .storage()
+            .instance()
+            .has(&DataKey::TokenMetadata(token_id))
+        {
+            token_id = token_id
+                .checked_add(1)
+                .ok_or(ContractError::SupplyLimitExceeded)?;
+        }
+        let next = token_id
+            .checked_add(1)
+            .ok_or(ContractError::SupplyLimitExceeded)?;
+        env.storage().instance().set(&DataKey::NextTokenId, &next);
+        Ok(token_id)
+    }
+
+    /// `base_uri` with the decimal `token_id` appended.
+    fn derived_uri(env: &Env, token_id: u32) -> Result<String, ContractError> {
+        let config: CollectionConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::CollectionConfig)
+            .unwrap();
+        let base_len = config.base_uri.len() as usize;
+        let mut buf = [0u8; MAX_DERIVED_URI_LEN];
+        if base_len + 10 > buf.len() {
+            return Err(ContractError::InvalidAmount);
+        }
+        config.base_uri.copy_into_slice(&mut buf[..base_len]);
+
+        let mut digits = [0u8; 10];
+        let mut start = digits.len();
+        let mut n = token_id;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        let len = base_len + digits.len() - start;
+        buf[base_len..len].copy_from_slice(&digits[start..]);
+        Ok(String::from_bytes(env, &buf[..len]))
+    }
+
+    /// Designated minters may always mint; everyone else depends on the mint
+    /// mode. Returns the mode to charge under, or `None` for minters.
+    fn authorize_minter(env: &Env, minter: &Address) -> Option<MintMode> {
+        if Self::is_minter(env, minter) {
+            return None;
+        }
+        let mode = Self::mint_mode(env.clone());
+        if mode == MintMode::MinterOnly {
+            panic_with_error!(env, ContractError::NotMinter);
+        }
+        Some(mode)
+    }
+
+    /// Adds `amount` to the total supply, enforcing the collection cap.
+    fn reserve_supply(env: &Env, amount: u32) -> Result<(), ContractError> {
         let config: CollectionConfig = env
             .storage()
             .instance()
@@ -86,42 +210,76 @@ impl NftCollection {
             .instance()
             .get(&DataKey::TotalSupply)
             .unwrap_or(0);
+        let new_supply = total_supply
+            .checked_add(amount)
+            .ok_or(ContractError::SupplyLimitExceeded)?;
 
         if let Some(max) = config.max_supply
-            && total_supply >= max
+            && new_supply > max
         {
             return Err(ContractError::SupplyLimitExceeded);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &new_supply);
+        Ok(())
+    }
 
+    /// Consumes whitelist quota or collects the public price for `amount` units.
+    fn charge_mint(
+        env: &Env,
+        minter: &Address,
+        mode: Option<MintMode>,
+        amount: u32,
+    ) -> Result<(), ContractError> {
+        match mode {
+            Some(MintMode::Whitelist) => {
+                let quota: u32 = read(env, DataKey::Whitelist(minter.clone()))
+                    .ok_or(ContractError::NotWhitelisted)?;
+                let minted = Self::whitelist_minted(env.clone(), minter.clone());
+                if minted.saturating_add(amount) > quota {
+                    return Err(ContractError::MintQuotaExceeded);
+                }
+                write(
+                    env,
+                    DataKey::WhitelistMinted(minter.clone()),
+                    &(minted + amount),
+                );
+            }
+            Some(MintMode::Public) => {
+                if let Some(price) = Self::mint_price(env.clone()) {
+                    let total = price.amount * amount as i128;
+                    token::Client::new(env, &price.asset).transfer(
+                        minter,
+                        env.current_contract_address(),
+                        &total,
+                    );
+                    let key = DataKey::Proceeds(price.asset);
+                    let proceeds: i128 = env.storage().instance().get(&key).unwrap_or(0);
+                    env.storage().instance().set(&key, &(proceeds + total));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn save_metadata(env: &Env, token_id: u32, uri: String, attributes: Vec<(String, String)>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
         let metadata = TokenMetadata {
             token_id,
-            uri: uri.clone(),
+            uri,
             attributes,
-            creator: admin.clone(),
+            creator: admin,
             created_at: env.ledger().timestamp(),
         };
-
         env.storage()
             .instance()
             .set(&DataKey::TokenMetadata(token_id), &metadata);
-        env.storage().instance().set(&DataKey::Owner(token_id), &to);
-
-        let balance: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Balance(to.clone(), token_id))
-            .unwrap_or(0);
-        env.storage()
-            .instance()
-            .set(&DataKey::Balance(to.clone(), token_id), &(balance + 1));
-
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &(total_supply + 1));
-
-        events::emit_mint(&env, env.current_contract_address(), to, token_id, 1);
-
-        Ok(())
     }
 
     pub fn transfer(
@@ -131,7 +289,29 @@ impl NftCollection {
         token_id: u32,
     ) -> Result<(), ContractError> {
         from.require_auth();
+        Self::do_transfer(&env, &from, from.clone(), to, token_id)
+    }
+
+    /// Moves `token_id` on behalf of its owner. `spender` must be the owner,
+    /// the token's approved address or an operator approved for all.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
+        spender.require_auth();
+        Self::do_transfer(&env, &spender, from, to, token_id)
+    }
 
+    fn do_transfer(
+        env: &Env,
+        spender: &Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
         if env
             .storage()
             .instance()
@@ -146,32 +326,21 @@ impl NftCollection {
             .instance()
             .get(&DataKey::Owner(token_id))
             .ok_or(ContractError::NotFound)?;
-        if owner != from {
+        if owner != from || !Self::is_approved_or_owner(env, spender, &owner, token_id) {
             return Err(ContractError::NotAuthorized);
         }
 
         env.storage().instance().set(&DataKey::Owner(token_id), &to);
-
-        let from_balance: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Balance(from.clone(), token_id))
-            .unwrap_or(0);
-        env.storage().instance().set(
-            &DataKey::Balance(from.clone(), token_id),
-            &(from_balance - 1),
-        );
-
-        let to_balance: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::Balance(to.clone(), token_id))
-            .unwrap_or(0);
         env.storage()
-            .instance()
-            .set(&DataKey::Balance(to.clone(), token_id), &(to_balance + 1));
+            .persistent()
+            .remove(&DataKey::Approved(token_id));
+
+        let from_balance = Self::balance_of(env.clone(), from.clone(), token_id);
+        Self::set_balance(env, &from, token_id, from_balance - 1);
+        let to_balance = Self::balance_of(env.clone(), to.clone(), token_id);
+        Self::set_balance(env, &to, token_id, to_balance + 1);
 
-        events::emit_transfer(&env, env.current_contract_address(), from, to, token_id, 1);
+        events::emit_transfer(env, env.current_contract_address(), from, to, token_id, 1);
 
         Ok(())
     }
@@ -189,26 +358,274 @@ impl NftCollection {
         }
 
         env.storage().instance().remove(&DataKey::Owner(token_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approved(token_id));
         env.storage()
             .instance()
             .remove(&DataKey::TokenMetadata(token_id));
 
-        let balance: u32 = env
+        let balance = Self::balance_of(env.clone(), from.clone(), token_id);
+        Self::set_balance(&env, &from, token_id, balance - 1);
+
+        let total_supply: u32 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &(total_supply - 1));
+
+        events::emit_burn(&env, env.current_contract_address(), from, token_id, 1);
+        Self::report_to_factory(&env, 0, 1);
+
+        Ok(())
+    }
+
+    /* Semi-Fungible Tokens */
+
+    /// Mints `amount` units of `token_id` to `to`. The first mint of an ID
+    /// records its metadata; later mints only add supply. Only designated
+    /// minters may create an ID.
+    pub fn mint_quantity(
+        env: Env,
+        minter: Address,
+        to: Address,
+        token_id: u32,
+        amount: u32,
+        uri: String,
+        attributes: Vec<(String, String)>,
+    ) -> Result<(), ContractError> {
+        minter.require_auth();
+        if !Self::is_semi_fungible(env.clone()) {
+            return Err(ContractError::WrongTokenMode);
+        }
+        if amount == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let mode = Self::authorize_minter(&env, &minter);
+        let exists = env
             .storage()
             .instance()
-            .get(&DataKey::Balance(from.clone(), token_id))
-            .unwrap_or(0);
+            .has(&DataKey::TokenMetadata(token_id));
+        if mode.is_some() && !exists {
+            return Err(ContractError::NotMinter);
+        }
+
+        let supply = Self::token_supply(env.clone(), token_id);
+        let new_supply = supply
+            .checked_add(amount)
+            .ok_or(ContractError::SupplyLimitExceeded)?;
+        if let Some(max) = Self::token_max_supply(env.clone(), token_id)
+            && new_supply > max
+        {
+            return Err(ContractError::SupplyLimitExceeded);
+        }
+        Self::reserve_supply(&env, amount)?;
+        Self::charge_mint(&env, &minter, mode, amount)?;
+
+        if !exists {
+            Self::save_metadata(&env, token_id, uri, attributes);
+        }
+        write(&env, DataKey::TokenSupply(token_id), &new_supply);
+        let balance = Self::balance_of(env.clone(), to.clone(), token_id);
+        Self::set_balance(&env, &to, token_id, balance + amount);
+
+        events::emit_mint(&env, env.current_contract_address(), to, token_id, amount);
+        Self::report_to_factory(&env, amount, 0);
+
+        Ok(())
+    }
+
+    /// Moves `amount` units of `token_id` from `from` to `to`. `spender` must
+    /// be `from` or an operator `from` approved for all.
+    pub fn transfer_amount(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        amount: u32,
+    ) -> Result<(), ContractError> {
+        spender.require_auth();
+        Self::check_quantity_transfer(&env, &spender, &from)?;
+        Self::move_amount(&env, &from, &to, token_id, amount)
+    }
+
+    /// Moves several `(token_id, amount)` pairs from `from` to `to`; all-or-nothing.
+    pub fn batch_transfer_amounts(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        items: Vec<(u32, u32)>,
+    ) -> Result<(), ContractError> {
+        spender.require_auth();
+        Self::check_quantity_transfer(&env, &spender, &from)?;
+        if items.is_empty() || items.len() > MAX_TOKEN_BATCH {
+            return Err(ContractError::InvalidAmount);
+        }
+        for (token_id, amount) in items.iter() {
+            Self::move_amount(&env, &from, &to, token_id, amount)?;
+        }
+        Ok(())
+    }
+
+    pub fn burn_amount(
+        env: Env,
+        from: Address,
+        token_id: u32,
+        amount: u32,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+        if !Self::is_semi_fungible(env.clone()) {
+            return Err(ContractError::WrongTokenMode);
+        }
+        let balance = Self::balance_of(env.clone(), from.clone(), token_id);
+        if amount == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+
+        Self::set_balance(&env, &from, token_id, balance - amount);
+        let supply = Self::token_supply(env.clone(), token_id);
+        write(&env, DataKey::TokenSupply(token_id), &(supply - amount));
+        let total_supply: u32 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
         env.storage()
             .instance()
-            .set(&DataKey::Balance(from.clone(), token_id), &(balance - 1));
+            .set(&DataKey::TotalSupply, &(total_supply - amount));
 
-        let total_supply: u32 = env.storage().instance().get(&DataKey::TotalSupply).unwrap();
+        events::emit_burn(&env, env.current_contract_address(), from, token_id, amount);
+        Self::report_to_factory(&env, 0, amount);
+
+        Ok(())
+    }
+
+    /// Caps the supply of one token ID; cannot go below what is already minted.
+    pub fn set_token_max_supply(
+        env: Env,
+        token_id: u32,
+        max_supply: u32,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        if !Self::is_semi_fungible(env.clone()) {
+            return Err(ContractError::WrongTokenMode);
+        }
+        if max_supply == 0 || max_supply < Self::token_supply(env.clone(), token_id) {
+            return Err(ContractError::InvalidAmount);
+        }
         env.storage()
             .instance()
-            .set(&DataKey::TotalSupply, &(total_supply - 1));
+            .set(&DataKey::TokenMaxSupply(token_id), &max_supply);
+        Ok(())
+    }
 
-        events::emit_burn(&env, env.current_contract_address(), from, token_id, 1);
+    pub fn token_supply(env: Env, token_id: u32) -> u32 {
+        read(&env, DataKey::TokenSupply(token_id)).unwrap_or(0)
+    }
+
+    pub fn token_max_supply(env: Env, token_id: u32) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenMaxSupply(token_id))
+    }
 
+    /// Balances for `owners[i]` of `token_ids[i]`.
+    pub fn balance_of_batch(
+        env: Env,
+        owners: Vec<Address>,
+        token_ids: Vec<u32>,
+    ) -> Result<Vec<u32>, ContractError> {
+        if owners.len() != token_ids.len() || owners.len() > MAX_TOKEN_BATCH {
+            return Err(ContractError::InvalidAmount);
+        }
+        let mut balances = Vec::new(&env);
+        for (owner, token_id) in owners.iter().zip(token_ids.iter()) {
+            balances.push_back(Self::balance_of(env.clone(), owner, token_id));
+        }
+        Ok(balances)
+    }
+
+    /// Switches between one-owner tokens and quantity tokens (ERC-1155
+    /// style). Only possible while no tokens are outstanding.
+    pub fn set_semi_fungible(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        if Self::total_supply(env.clone()) > 0 {
+            return Err(ContractError::WrongTokenMode);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::SemiFungible, &enabled);
+        Ok(())
+    }
+
+    pub fn is_semi_fungible(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::SemiFungible)
+            .unwrap_or(false)
+    }
+
+    fn check_quantity_transfer(
+        env: &Env,
+        spender: &Address,
+        from: &Address,
+    ) -> Result<(), ContractError> {
+        if !Self::is_semi_fungible(env.clone()) {
+            return Err(ContractError::WrongTokenMode);
+        }
+        if env
+            .storage()
+            .instance()
+            .get::<_, bool>(&DataKey::IsPaused)
+            .unwrap_or(false)
+        {
+            return Err(ContractError::ContractPaused);
+        }
+        if spender != from && !Self::is_approved_for_all(env.clone(), from.clone(), spender.clone())
+        {
+            return Err(ContractError::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    fn move_amount(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        token_id: u32,
+        amount: u32,
+    ) -> Result<(), ContractError> {
+        if amount == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let from_balance = Self::balance_of(env.clone(), from.clone(), token_id);
+        if from_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+        Self::set_balance(env, from, token_id, from_balance - amount);
+        let to_balance = Self::balance_of(env.clone(), to.clone(), token_id);
+        Self::set_balance(env, to, token_id, to_balance + amount);
+
+        events::emit_transfer(
+            env,
+            env.current_contract_address(),
+            from.clone(),
+            to.clone(),
+            token_id,
+            amount,
+        );
         Ok(())
     }
 
@@ -234,16 +651,92 @@ impl NftCollection {
     }
 
     pub fn balance_of(env: Env, owner: Address, token_id: u32) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::Balance(owner, token_id))
+        let key = DataKey::Balance(owner, token_id);
+        // Collections upgraded from the instance layout keep older balances
+        // there until they next change
+        read(&env, key.clone())
+            .or_else(|| env.storage().instance().get(&key))
             .unwrap_or(0)
     }
 
+    /// Stores `owner`'s balance of `token_id`, dropping the entry once empty.
+    fn set_balance(env: &Env, owner: &Address, token_id: u32, balance: u32) {
+        let key = DataKey::Balance(owner.clone(), token_id);
+        if env.storage().instance().has(&key) {
+            env.storage().instance().remove(&key);
+        }
+        if balance == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            write(env, key, &balance);
+        }
+    }
+
     pub fn owner_of(env: Env, token_id: u32) -> Option<Address> {
         env.storage().instance().get(&DataKey::Owner(token_id))
     }
 
+    /* Approvals */
+
+    pub fn approve(
+        env: Env,
+        owner: Address,
+        approved: Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        let current: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Owner(token_id))
+            .ok_or(ContractError::NotFound)?;
+        if current != owner {
+            return Err(ContractError::NotAuthorized);
+        }
+
+        write(&env, DataKey::Approved(token_id), &approved);
+        events::emit_approval(
+            &env,
+            env.current_contract_address(),
+            owner,
+            approved,
+            token_id,
+        );
+        Ok(())
+    }
+
+    pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
+        owner.require_auth();
+        let key = DataKey::OperatorApproval(owner.clone(), operator.clone());
+        if approved {
+            write(&env, key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::emit_approval_for_all(
+            &env,
+            env.current_contract_address(),
+            owner,
+            operator,
+            approved,
+        );
+    }
+
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        read(&env, DataKey::Approved(token_id))
+    }
+
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        read(&env, DataKey::OperatorApproval(owner, operator)).unwrap_or(false)
+    }
+
+    fn is_approved_or_owner(env: &Env, spender: &Address, owner: &Address, token_id: u32) -> bool {
+        spender == owner
+            || Self::get_approved(env.clone(), token_id).as_ref() == Some(spender)
+            || Self::is_approved_for_all(env.clone(), owner.clone(), spender.clone())
+    }
+
     pub fn set_royalty_info(
         env: Env,
         recipient: Address,
@@ -297,6 +790,124 @@ impl NftCollection {
             .set(&DataKey::Minter(minter), &is_minter);
     }
 
+    /* Mint Modes */
+
+    pub fn set_mint_mode(env: Env, mode: MintMode) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::MintMode, &mode);
+        events::emit_mint_mode_updated(&env, env.current_contract_address(), mode);
+    }
+
+    pub fn mint_mode(env: Env) -> MintMode {
+        env.storage()
+            .instance()
+            .get(&DataKey::MintMode)
+            .unwrap_or(MintMode::MinterOnly)
+    }
+
+    /// Sets the public mint price; an `amount` of zero makes public minting free.
+    pub fn set_mint_price(env: Env, asset: Address, amount: i128) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        if amount < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if amount == 0 {
+            env.storage().instance().remove(&DataKey::MintPrice);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::MintPrice, &MintPrice { asset, amount });
+        }
+        Ok(())
+    }
+
+    pub fn mint_price(env: Env) -> Option<MintPrice> {
+        env.storage().instance().get(&DataKey::MintPrice)
+    }
+
+    /// Sets whitelist quotas in bulk. A quota of zero removes the address;
+    /// mints already made against a quota are kept.
+    pub fn set_whitelist(env: Env, entries: Vec<(Address, u32)>) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        if entries.len() > MAX_WHITELIST_BATCH {
+            return Err(ContractError::InvalidAmount);
+        }
+        for (address, quota) in entries.iter() {
+            if quota == 0 {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::Whitelist(address));
+            } else {
+                write(&env, DataKey::Whitelist(address), &quota);
+            }
+        }
+
+        events::emit_whitelist_updated(&env, env.current_contract_address(), entries.len());
+        Ok(())
+    }
+
+    pub fn whitelist_quota(env: Env, address: Address) -> u32 {
+        read(&env, DataKey::Whitelist(address)).unwrap_or(0)
+    }
+
+    pub fn whitelist_minted(env: Env, address: Address) -> u32 {
+        read(&env, DataKey::WhitelistMinted(address)).unwrap_or(0)
+    }
+
+    pub fn proceeds(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Proceeds(asset))
+            .unwrap_or(0)
+    }
+
+    /// Sends all public mint proceeds held in `asset` to the creator.
+    pub fn withdraw_proceeds(env: Env, asset: Address) -> i128 {
+        let creator = Self::get_creator(env.clone());
+        creator.require_auth();
+
+        let amount = Self::proceeds(env.clone(), asset.clone());
+        if amount > 0 {
+            env.storage()
+                .instance()
+                .remove(&DataKey::Proceeds(asset.clone()));
+            token::Client::new(&env, &asset).transfer(
+                &env.current_contract_address(),
+                &creator,
+                &amount,
+            );
+            events::emit_proceeds_withdrawn(
+                &env,
+                env.current_contract_address(),
+                asset,
+                creator,
+                amount,
+            );
+        }
+        amount
+    }
+
+    pub fn get_creator(env: Env) -> Address {
+        env.storage().instance().get(&DataKey::Creator).unwrap()
+    }
+
     fn is_minter(env: &Env, address: &Address) -> bool {
         env.storage()
             .instance()
@@ -304,6 +915,42 @@ impl NftCollection {
             .unwrap_or(false)
     }
 
+    /// Swaps this collection's code. Only the deploying factory may upgrade.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let factory: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAddress)
+            .unwrap();
+        factory.require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Reports supply changes to the deploying factory. Collections initialised
+    /// with a non-contract factory address (standalone use) have nobody to tell.
+    /// The report is best effort: a factory that rejects or fails it never
+    /// blocks the mint or burn that triggered it.
+    fn report_to_factory(env: &Env, minted: u32, burned: u32) {
+        let factory: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAddress)
+            .unwrap();
+        if !matches!(factory.executable(), Some(Executable::Wasm(_))) {
+            return;
+        }
+        let _ = env.try_invoke_contract::<(), InvokeError>(
+            &factory,
+            &Symbol::new(env, "report_activity"),
+            vec![
+                env,
+                env.current_contract_address().into_val(env),
+                minted.into_val(env),
+                burned.into_val(env),
+            ],
+        );
+    }
+
     pub fn get_factory(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::FactoryAddress)
     }
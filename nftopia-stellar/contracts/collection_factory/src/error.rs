@@ -17,4 +17,15 @@ pub enum ContractError {
     InvalidRecipient = 11,
     TokenAlreadyExists = 12,
     MaxCollectionsExceeded = 13,
+    TemplateNotFound = 14,
+    TemplateDeprecated = 15,
+    InvalidVersion = 16,
+    UpgradeOptedOut = 17,
+    NotWhitelisted = 18,
+    MintQuotaExceeded = 19,
+    WrongTokenMode = 20,
+    TierNotFound = 21,
+    TemplateNotAllowed = 22,
+    CollectionAlreadyExists = 23,
+    FeeAssetNotAccepted = 24,
 }
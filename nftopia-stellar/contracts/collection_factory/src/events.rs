@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, Env, contractevent};
+use crate::types::{MintMode, SemVer};
+use soroban_sdk::{Address, BytesN, Env, String, contractevent};
 
 #[contractevent]
 #[derive(Clone, Debug)]
@@ -66,6 +67,137 @@ pub struct CollectionCountReset {
     pub creator: Address,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TemplateRegistered {
+    pub template_id: String,
+    pub version: SemVer,
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TemplateDeprecated {
+    pub template_id: String,
+    pub version: SemVer,
+    pub deprecated: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CollectionUpgraded {
+    pub collection: Address,
+    pub id: u32,
+    pub from_version: SemVer,
+    pub to_version: SemVer,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UpgradeBatch {
+    pub template_id: String,
+    pub version: SemVer,
+    pub start_id: u32,
+    pub next_id: u32,
+    pub upgraded: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UpgradeOptOutSet {
+    pub collection: Address,
+    pub id: u32,
+    pub opt_out: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct MintModeUpdated {
+    pub collection: Address,
+    pub mode: MintMode,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct WhitelistUpdated {
+    pub collection: Address,
+    pub entries: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ProceedsWithdrawn {
+    pub collection: Address,
+    pub asset: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct Approval {
+    pub collection: Address,
+    pub owner: Address,
+    pub approved: Address,
+    pub token_id: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ApprovalForAll {
+    pub collection: Address,
+    pub owner: Address,
+    pub operator: Address,
+    pub approved: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CreatorTierChanged {
+    pub creator: Address,
+    pub old_tier: u32,
+    pub new_tier: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TierConfigured {
+    pub tier: u32,
+    pub max_collections: u32,
+    pub overflow_fee: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CreationFeeSet {
+    pub asset: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ReferralShareSet {
+    pub share_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CreationFeePaid {
+    pub creator: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub referrer: Option<Address>,
+    pub referral_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct FeesWithdrawn {
+    pub asset: Address,
+    pub to: Address,
+    pub amount: i128,
+}
+
 /* Emission Helpers */
 
 pub fn emit_collection_created(
@@ -150,3 +282,177 @@ pub fn emit_creator_limit_exceeded_attempt(env: &Env, creator: Address, current_
 pub fn emit_collection_count_reset(env: &Env, creator: Address) {
     CollectionCountReset { creator }.publish(env);
 }
+
+pub fn emit_template_registered(
+    env: &Env,
+    template_id: String,
+    version: SemVer,
+    wasm_hash: BytesN<32>,
+) {
+    TemplateRegistered {
+        template_id,
+        version,
+        wasm_hash,
+    }
+    .publish(env);
+}
+
+pub fn emit_template_deprecated(env: &Env, template_id: String, version: SemVer, deprecated: bool) {
+    TemplateDeprecated {
+        template_id,
+        version,
+        deprecated,
+    }
+    .publish(env);
+}
+
+pub fn emit_collection_upgraded(
+    env: &Env,
+    collection: Address,
+    id: u32,
+    from_version: SemVer,
+    to_version: SemVer,
+) {
+    CollectionUpgraded {
+        collection,
+        id,
+        from_version,
+        to_version,
+    }
+    .publish(env);
+}
+
+pub fn emit_upgrade_batch(
+    env: &Env,
+    template_id: String,
+    version: SemVer,
+    start_id: u32,
+    next_id: u32,
+    upgraded: u32,
+) {
+    UpgradeBatch {
+        template_id,
+        version,
+        start_id,
+        next_id,
+        upgraded,
+    }
+    .publish(env);
+}
+
+pub fn emit_upgrade_opt_out_set(env: &Env, collection: Address, id: u32, opt_out: bool) {
+    UpgradeOptOutSet {
+        collection,
+        id,
+        opt_out,
+    }
+    .publish(env);
+}
+
+pub fn emit_mint_mode_updated(env: &Env, collection: Address, mode: MintMode) {
+    MintModeUpdated { collection, mode }.publish(env);
+}
+
+pub fn emit_whitelist_updated(env: &Env, collection: Address, entries: u32) {
+    WhitelistUpdated {
+        collection,
+        entries,
+    }
+    .publish(env);
+}
+
+pub fn emit_proceeds_withdrawn(
+    env: &Env,
+    collection: Address,
+    asset: Address,
+    to: Address,
+    amount: i128,
+) {
+    ProceedsWithdrawn {
+        collection,
+        asset,
+        to,
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_approval(
+    env: &Env,
+    collection: Address,
+    owner: Address,
+    approved: Address,
+    token_id: u32,
+) {
+    Approval {
+        collection,
+        owner,
+        approved,
+        token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_approval_for_all(
+    env: &Env,
+    collection: Address,
+    owner: Address,
+    operator: Address,
+    approved: bool,
+) {
+    ApprovalForAll {
+        collection,
+        owner,
+        operator,
+        approved,
+    }
+    .publish(env);
+}
+
+pub fn emit_creator_tier_changed(env: &Env, creator: Address, old_tier: u32, new_tier: u32) {
+    CreatorTierChanged {
+        creator,
+        old_tier,
+        new_tier,
+    }
+    .publish(env);
+}
+
+pub fn emit_tier_configured(env: &Env, tier: u32, max_collections: u32, overflow_fee: i128) {
+    TierConfigured {
+        tier,
+        max_collections,
+        overflow_fee,
+    }
+    .publish(env);
+}
+
+pub fn emit_creation_fee_set(env: &Env, asset: Address, amount: i128) {
+    CreationFeeSet { asset, amount }.publish(env);
+}
+
+pub fn emit_referral_share_set(env: &Env, share_bps: u32) {
+    ReferralShareSet { share_bps }.publish(env);
+}
+
+pub fn emit_creation_fee_paid(
+    env: &Env,
+    creator: Address,
+    asset: Address,
+    amount: i128,
+    referrer: Option<Address>,
+    referral_amount: i128,
+) {
+    CreationFeePaid {
+        creator,
+        asset,
+        amount,
+        referrer,
+        referral_amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_fees_withdrawn(env: &Env, asset: Address, to: Address, amount: i128) {
+    FeesWithdrawn { asset, to, amount }.publish(env);
+}
@@ -1,11 +1,15 @@
 use crate::error::ContractError;
 use crate::events;
-use crate::storage::DataKey;
-use crate::types::{CollectionConfig, CollectionInfo};
+use crate::fees;
+use crate::registry;
+use crate::storage::{DataKey, MAX_PAGE_SIZE, MAX_UPGRADE_BATCH, read, write};
+use crate::templates;
+use crate::tiers;
+use crate::types::{CollectionConfig, CollectionInfo, FactoryStats, SemVer, Template, TierConfig};
+use crate::upgrades;
 use crate::version;
 use soroban_sdk::{
     Address, BytesN, Env, IntoVal, String, Val, Vec, contract, contractimpl, panic_with_error,
-    token,
 };
 
 #[contract]
@@ -32,12 +36,17 @@ impl CollectionFactory {
         env.storage().instance().set(&DataKey::FeeAsset, &fee_asset);
     }
 
+    /// Deploys a collection from the newest non-deprecated version of `template_id`.
+    /// When creation fees are configured, `fee_asset` picks which accepted asset
+    /// pays; `referrer` receives the referral share of that fee.
     pub fn create_collection(
         env: Env,
         creator: Address,
-        wasm_hash: BytesN<32>,
+        template_id: String,
         salt: BytesN<32>,
         config: CollectionConfig,
+        fee_asset: Option<Address>,
+        referrer: Option<Address>,
     ) -> Result<Address, ContractError> {
         creator.require_auth();
 
@@ -47,25 +56,27 @@ impl CollectionFactory {
             .get(&DataKey::FactoryAdmin)
             .unwrap();
 
-        // Check creator boundaries
-        let current_creator_count: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::CreatorCollectionCount(creator.clone()))
-            .unwrap_or(0);
+        // The creator's tier decides which templates, royalties and limits apply
+        let creator_tier = tiers::get_tier(&env, &creator);
+        let tier = tiers::get_config(&env, creator_tier).ok_or(ContractError::TierNotFound)?;
+        tiers::check_collection(&tier, &template_id, config.royalty_percentage)?;
 
-        let max_allowed: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxCollectionsPerCreator)
-            .unwrap_or(10);
+        let template = templates::latest_active(&env, &template_id)?;
+
+        // A reused salt would collide with an existing contract; fail clearly
+        // instead of letting the deploy trap
+        let predicted =
+            Self::predict_collection_address(env.clone(), creator.clone(), salt.clone());
+        if registry::get_id(&env, predicted.clone()).is_some() || predicted.exists() {
+            return Err(ContractError::CollectionAlreadyExists);
+        }
 
-        if current_creator_count >= max_allowed {
-            let fee_amount: i128 = env
-                .storage()
-                .instance()
-                .get(&DataKey::FactoryFee)
-                .unwrap_or(0);
+        // Check creator boundaries
+        let current_creator_count: u32 =
+            read(&env, DataKey::CreatorCollectionCount(creator.clone())).unwrap_or(0);
+
+        if current_creator_count >= tier.max_collections {
+            let fee_amount = tier.overflow_fee;
 
             // If fee is zero or less, overflow tier is considered locked
             if fee_amount <= 0 {
@@ -78,18 +89,10 @@ impl CollectionFactory {
             }
 
             // Charge overflow fee to allow deployment
-            let fee_asset: Address = env.storage().instance().get(&DataKey::FeeAsset).unwrap();
-            let token_client = token::Client::new(&env, &fee_asset);
-
-            // Transfer overflow fee from creator to factory instance
-            token_client.transfer(&creator, env.current_contract_address(), &fee_amount);
+            fees::charge_overflow_fee(&env, &creator, fee_amount)?;
         }
 
-        let collection_id: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::CollectionCount)
-            .unwrap_or(0);
+        fees::charge_creation_fee(&env, &creator, fee_asset, referrer)?;
 
         let constructor_args: Vec<Val> = Vec::new(&env);
 
@@ -97,7 +100,7 @@ impl CollectionFactory {
         let collection_address = env
             .deployer()
             .with_address(creator.clone(), salt)
-            .deploy_v2(wasm_hash, constructor_args);
+            .deploy_v2(template.wasm_hash, constructor_args);
 
         // Initialize the collection
         env.invoke_contract::<()>(
@@ -107,6 +110,7 @@ impl CollectionFactory {
                 &env,
                 admin.into_val(&env),
                 env.current_contract_address().into_val(&env),
+                creator.clone().into_val(&env),
                 config.clone().into_val(&env)
             ],
         );
@@ -117,23 +121,22 @@ impl CollectionFactory {
             config: config.clone(),
             created_at: env.ledger().timestamp(),
             total_tokens: 0,
+            template_id,
+            template_version: template.version,
+            upgrade_opt_out: false,
+            total_mints: 0,
+            total_burns: 0,
+            last_activity: 0,
+            creator_tier,
         };
 
         // Persist records
-        env.storage().instance().set(
-            &DataKey::CollectionAddress(collection_id),
-            &collection_address,
-        );
-        env.storage()
-            .instance()
-            .set(&DataKey::CollectionInfo(collection_id), &info);
-        env.storage()
-            .instance()
-            .set(&DataKey::CollectionCount, &(collection_id + 1));
+        let collection_id = registry::add_collection(&env, &info);
 
         // Increment unique creator address metrics
-        env.storage().instance().set(
-            &DataKey::CreatorCollectionCount(creator.clone()),
+        write(
+            &env,
+            DataKey::CreatorCollectionCount(creator.clone()),
             &(current_creator_count + 1),
         );
 
@@ -147,6 +150,13 @@ impl CollectionFactory {
         Ok(collection_address)
     }
 
+    /// Address `create_collection` will deploy to for this creator and salt.
+    pub fn predict_collection_address(env: Env, creator: Address, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_address(creator, salt)
+            .deployed_address()
+    }
+
     pub fn verify_factory_origin(env: Env, collection: Address) -> bool {
         // Check if the collection was deployed by this factory
         // by calling is_from_factory on the collection
@@ -158,21 +168,252 @@ impl CollectionFactory {
         result
     }
 
+    /// Deprecated: only the first `MAX_PAGE_SIZE` collections. Use `list_collections`.
     pub fn get_collections_by_factory(env: Env) -> Vec<Address> {
-        let count = env
+        registry::list(&env, 0, MAX_PAGE_SIZE)
+    }
+
+    /// Collection addresses with IDs `offset..offset + limit` (at most `MAX_PAGE_SIZE`).
+    pub fn list_collections(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        registry::list(&env, offset, limit.min(MAX_PAGE_SIZE))
+    }
+
+    /// `creator`'s collections in deployment order (at most `MAX_PAGE_SIZE`).
+    pub fn list_collections_by_creator(
+        env: Env,
+        creator: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        registry::list_by_creator(&env, &creator, offset, limit.min(MAX_PAGE_SIZE))
+    }
+
+    /// Callback through which a deployed collection reports minted and burned
+    /// token counts. Only registered collections, calling for themselves, count.
+    pub fn report_activity(
+        env: Env,
+        collection: Address,
+        minted: u32,
+        burned: u32,
+    ) -> Result<(), ContractError> {
+        collection.require_auth();
+        registry::record_activity(&env, collection, minted, burned)
+    }
+
+    pub fn get_factory_stats(env: Env) -> FactoryStats {
+        registry::stats(&env)
+    }
+
+    /* Template Registry */
+
+    /// Approves `wasm_hash` as `version` of `template_id`; versions must increase.
+    pub fn register_template(
+        env: Env,
+        template_id: String,
+        version: SemVer,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
             .storage()
             .instance()
-            .get(&DataKey::CollectionCount)
-            .unwrap_or(0);
-        let mut collections = Vec::new(&env);
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        templates::register(
+            &env,
+            template_id.clone(),
+            version.clone(),
+            wasm_hash.clone(),
+        )?;
+        events::emit_template_registered(&env, template_id, version, wasm_hash);
+        Ok(())
+    }
+
+    /// Deprecated versions can no longer be used for new collections.
+    pub fn set_template_deprecated(
+        env: Env,
+        template_id: String,
+        version: SemVer,
+        deprecated: bool,
+    ) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        templates::set_deprecated(&env, template_id.clone(), version.clone(), deprecated)?;
+        events::emit_template_deprecated(&env, template_id, version, deprecated);
+        Ok(())
+    }
+
+    pub fn get_template(env: Env, template_id: String) -> Option<Template> {
+        templates::get_template(&env, &template_id)
+    }
 
-        for i in 0..count {
-            if let Some(address) = env.storage().instance().get(&DataKey::CollectionAddress(i)) {
-                collections.push_back(address);
+    /* Collection Upgrades */
+
+    /// Upgrades one collection to `version` of the template it was deployed from.
+    pub fn upgrade_collection(env: Env, id: u32, version: SemVer) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        let info = registry::get_info(&env, id).ok_or(ContractError::CollectionNotFound)?;
+        let target = templates::active_version(&env, &info.template_id, &version)?;
+        upgrades::upgrade_collection(&env, id, info, &target)
+    }
+
+    /// Upgrades up to `limit` collections of `template_id`, scanning IDs from
+    /// `start_id`. Opted-out and already-current collections are skipped.
+    /// Returns the ID to resume from; it equals the collection count once done.
+    pub fn upgrade_collections(
+        env: Env,
+        template_id: String,
+        version: SemVer,
+        start_id: u32,
+        limit: u32,
+    ) -> Result<u32, ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        let target = templates::active_version(&env, &template_id, &version)?;
+        let end = start_id
+            .saturating_add(limit.min(MAX_UPGRADE_BATCH))
+            .min(registry::collection_count(&env));
+
+        let mut upgraded = 0u32;
+        for id in start_id..end {
+            let Some(info) = registry::get_info(&env, id) else {
+                continue;
+            };
+            if info.template_id != template_id || !upgrades::is_eligible(&info, &target) {
+                continue;
             }
+            upgrades::upgrade_collection(&env, id, info, &target)?;
+            upgraded += 1;
+        }
+
+        let next_id = end.max(start_id);
+        events::emit_upgrade_batch(&env, template_id, version, start_id, next_id, upgraded);
+        Ok(next_id)
+    }
+
+    /// Lets a collection's creator refuse (or re-accept) factory upgrades.
+    pub fn set_upgrade_opt_out(
+        env: Env,
+        creator: Address,
+        id: u32,
+        opt_out: bool,
+    ) -> Result<(), ContractError> {
+        creator.require_auth();
+
+        let mut info = registry::get_info(&env, id).ok_or(ContractError::CollectionNotFound)?;
+        if info.creator != creator {
+            return Err(ContractError::NotAuthorized);
         }
+        info.upgrade_opt_out = opt_out;
+        registry::set_info(&env, id, &info);
+
+        events::emit_upgrade_opt_out_set(&env, info.address, id, opt_out);
+        Ok(())
+    }
+
+    /* Creator Tiers */
+
+    /// Defines or replaces the limits, fees, templates and royalty bounds of `tier`.
+    pub fn set_tier_config(env: Env, tier: u32, config: TierConfig) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
 
-        collections
+        tiers::set_config(&env, tier, &config)?;
+        events::emit_tier_configured(&env, tier, config.max_collections, config.overflow_fee);
+        Ok(())
+    }
+
+    pub fn get_tier_config(env: Env, tier: u32) -> Option<TierConfig> {
+        tiers::get_config(&env, tier)
+    }
+
+    pub fn set_creator_tier(env: Env, creator: Address, tier: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        let old_tier = tiers::set_tier(&env, &creator, tier)?;
+        events::emit_creator_tier_changed(&env, creator, old_tier, tier);
+        Ok(())
+    }
+
+    pub fn get_creator_tier(env: Env, creator: Address) -> u32 {
+        tiers::get_tier(&env, &creator)
+    }
+
+    /* Creation Fees */
+
+    /// Sets the base creation fee payable in `asset`; zero stops accepting it.
+    pub fn set_creation_fee(env: Env, asset: Address, amount: i128) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        fees::set_creation_fee(&env, asset.clone(), amount)?;
+        events::emit_creation_fee_set(&env, asset, amount);
+        Ok(())
+    }
+
+    /// Share of the creation fee paid to referrers, in basis points.
+    pub fn set_referral_share(env: Env, share_bps: u32) -> Result<(), ContractError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryAdmin)
+            .unwrap();
+        admin.require_auth();
+
+        fees::set_referral_share(&env, share_bps)?;
+        events::emit_referral_share_set(&env, share_bps);
+        Ok(())
+    }
+
+    pub fn get_fee_assets(env: Env) -> Vec<Address> {
+        fees::fee_assets(&env)
+    }
+
+    pub fn get_creation_fee(env: Env, asset: Address) -> i128 {
+        fees::creation_fee(&env, &asset)
+    }
+
+    pub fn get_referral_share(env: Env) -> u32 {
+        fees::referral_share(&env)
+    }
+
+    /// Lifetime fees kept by the factory in `asset`, net of referral payouts.
+    pub fn get_fees_collected(env: Env, asset: Address) -> i128 {
+        fees::fees_collected(&env, &asset)
+    }
+
+    pub fn get_referral_earnings(env: Env, referrer: Address, asset: Address) -> i128 {
+        fees::referral_earnings(&env, &referrer, &asset)
     }
 
     /* Operational Admin Functions */
@@ -219,15 +460,18 @@ impl CollectionFactory {
             .unwrap();
         admin.require_auth();
 
-        env.storage()
-            .instance()
-            .set(&DataKey::CreatorCollectionCount(creator.clone()), &0u32);
+        write(
+            &env,
+            DataKey::CreatorCollectionCount(creator.clone()),
+            &0u32,
+        );
 
         events::emit_collection_count_reset(&env, creator);
     }
 
     /* Getter Views */
 
+    /// Collection limit of an unconfigured default tier.
     pub fn get_max_collections(env: Env) -> u32 {
         env.storage()
             .instance()
@@ -236,42 +480,34 @@ impl CollectionFactory {
     }
 
     pub fn get_creator_collection_count(env: Env, creator: Address) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::CreatorCollectionCount(creator))
-            .unwrap_or(0)
+        read(&env, DataKey::CreatorCollectionCount(creator)).unwrap_or(0)
     }
 
+    /// Collections `creator` may still deploy without paying their tier's overflow fee.
     pub fn get_remaining_count(env: Env, creator: Address) -> u32 {
-        let max_allowed: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxCollectionsPerCreator)
-            .unwrap_or(10);
-        let current_count = env
-            .storage()
-            .instance()
-            .get(&DataKey::CreatorCollectionCount(creator))
+        let max_allowed = tiers::get_config(&env, tiers::get_tier(&env, &creator))
+            .map(|tier| tier.max_collections)
             .unwrap_or(0);
+        let current_count = read(&env, DataKey::CreatorCollectionCount(creator)).unwrap_or(0);
 
         max_allowed.saturating_sub(current_count)
     }
 
     pub fn get_collection_count(env: Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::CollectionCount)
-            .unwrap_or(0)
+        registry::collection_count(&env)
     }
 
     pub fn get_collection_address(env: Env, id: u32) -> Option<Address> {
-        env.storage()
-            .instance()
-            .get(&DataKey::CollectionAddress(id))
+        registry::get_address(&env, id)
     }
 
     pub fn get_collection_info(env: Env, id: u32) -> Option<CollectionInfo> {
-        env.storage().instance().get(&DataKey::CollectionInfo(id))
+        registry::get_info(&env, id)
+    }
+
+    /// Reverse lookup from a deployed collection address to its registry ID.
+    pub fn get_collection_id(env: Env, collection: Address) -> Option<u32> {
+        registry::get_id(&env, collection)
     }
 
     pub fn set_admin(env: Env, new_admin: Address) {
@@ -286,7 +522,13 @@ impl CollectionFactory {
             .set(&DataKey::FactoryAdmin, &new_admin);
     }
 
-    pub fn withdraw_fees(env: Env, to: Address) -> Result<(), ContractError> {
+    /// Withdraws `amount` of collected fees held in `asset`.
+    pub fn withdraw_fees(
+        env: Env,
+        to: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
         let admin: Address = env
             .storage()
             .instance()
@@ -298,15 +540,7 @@ impl CollectionFactory {
         }
         admin.require_auth();
 
-        let fee_asset: Address = env.storage().instance().get(&DataKey::FeeAsset).unwrap();
-        let token_client = token::Client::new(&env, &fee_asset);
-        let balance = token_client.balance(&env.current_contract_address());
-
-        if balance > 0 {
-            token_client.transfer(&env.current_contract_address(), &to, &balance);
-        }
-
-        Ok(())
+        fees::withdraw(&env, &to, asset, amount)
     }
 
     // -------------------------------------------------------------------------
@@ -0,0 +1,172 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{DataKey, MAX_FEE_ASSETS, read, write};
+use soroban_sdk::{Address, Env, Vec, token};
+
+pub fn fee_assets(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::FeeAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn creation_fee(env: &Env, asset: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::CreationFee(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Sets the base creation fee payable in `asset`. Zero stops accepting it.
+pub fn set_creation_fee(env: &Env, asset: Address, amount: i128) -> Result<(), ContractError> {
+    if amount < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    let mut assets = fee_assets(env);
+    let index = assets.first_index_of(&asset);
+    if amount == 0 {
+        if let Some(index) = index {
+            assets.remove(index);
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::CreationFee(asset));
+    } else {
+        if index.is_none() {
+            if assets.len() >= MAX_FEE_ASSETS {
+                return Err(ContractError::InvalidAmount);
+            }
+            assets.push_back(asset.clone());
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CreationFee(asset), &amount);
+    }
+    env.storage().instance().set(&DataKey::FeeAssets, &assets);
+    Ok(())
+}
+
+pub fn referral_share(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReferralShare)
+        .unwrap_or(0)
+}
+
+pub fn set_referral_share(env: &Env, share_bps: u32) -> Result<(), ContractError> {
+    if share_bps > 10000 {
+        return Err(ContractError::InvalidAmount);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::ReferralShare, &share_bps);
+    Ok(())
+}
+
+// Fee totals are keyed per asset and per referrer, so they live in persistent
+// storage rather than growing the instance entry.
+
+pub fn fees_collected(env: &Env, asset: &Address) -> i128 {
+    read(env, DataKey::FeesCollected(asset.clone())).unwrap_or(0)
+}
+
+pub fn referral_earnings(env: &Env, referrer: &Address, asset: &Address) -> i128 {
+    read(
+        env,
+        DataKey::ReferralEarnings(referrer.clone(), asset.clone()),
+    )
+    .unwrap_or(0)
+}
+
+fn record_collected(env: &Env, asset: &Address, amount: i128) -> Result<(), ContractError> {
+    let total = fees_collected(env, asset)
+        .checked_add(amount)
+        .ok_or(ContractError::InvalidAmount)?;
+    write(env, DataKey::FeesCollected(asset.clone()), &total);
+    Ok(())
+}
+
+/// Charges the base creation fee, if any asset has one configured, in the
+/// creator's chosen `fee_asset`. A referrer receives the referral share of it
+/// straight from the creator; the rest goes to the factory.
+pub fn charge_creation_fee(
+    env: &Env,
+    creator: &Address,
+    fee_asset: Option<Address>,
+    referrer: Option<Address>,
+) -> Result<(), ContractError> {
+    if fee_assets(env).is_empty() {
+        return Ok(());
+    }
+    let asset = fee_asset.ok_or(ContractError::FeeAssetNotAccepted)?;
+    let amount = creation_fee(env, &asset);
+    if amount == 0 {
+        return Err(ContractError::FeeAssetNotAccepted);
+    }
+
+    let token_client = token::Client::new(env, &asset);
+    let mut referral_amount = 0;
+    if let Some(referrer) = &referrer {
+        if referrer == creator {
+            return Err(ContractError::InvalidRecipient);
+        }
+        referral_amount = amount
+            .checked_mul(referral_share(env) as i128)
+            .ok_or(ContractError::InvalidAmount)?
+            / 10000;
+        if referral_amount > 0 {
+            let earnings = referral_earnings(env, referrer, &asset)
+                .checked_add(referral_amount)
+                .ok_or(ContractError::InvalidAmount)?;
+            token_client.transfer(creator, referrer, &referral_amount);
+            write(
+                env,
+                DataKey::ReferralEarnings(referrer.clone(), asset.clone()),
+                &earnings,
+            );
+        }
+    }
+
+    let factory_amount = amount - referral_amount;
+    token_client.transfer(creator, env.current_contract_address(), &factory_amount);
+    record_collected(env, &asset, factory_amount)?;
+
+    events::emit_creation_fee_paid(
+        env,
+        creator.clone(),
+        asset,
+        amount,
+        referrer,
+        referral_amount,
+    );
+    Ok(())
+}
+
+/// Charges a tier overflow fee in the legacy `FeeAsset`.
+pub fn charge_overflow_fee(
+    env: &Env,
+    creator: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let fee_asset: Address = env.storage().instance().get(&DataKey::FeeAsset).unwrap();
+    token::Client::new(env, &fee_asset).transfer(creator, env.current_contract_address(), &amount);
+    record_collected(env, &fee_asset, amount)
+}
+
+pub fn withdraw(
+    env: &Env,
+    to: &Address,
+    asset: Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if amount <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    let token_client = token::Client::new(env, &asset);
+    if token_client.balance(&env.current_contract_address()) < amount {
+        return Err(ContractError::InsufficientBalance);
+    }
+    token_client.transfer(&env.current_contract_address(), to, &amount);
+    events::emit_fees_withdrawn(env, asset, to.clone(), amount);
+    Ok(())
+}
@@ -3,8 +3,13 @@ pub mod collection;
 pub mod error;
 pub mod events;
 pub mod factory;
+pub mod fees;
+pub mod registry;
 pub mod storage;
+pub mod templates;
+pub mod tiers;
 pub mod types;
+pub mod upgrades;
 pub mod version;
 
 pub use crate::collection::NftCollection;
@@ -0,0 +1,142 @@
+use crate::error::ContractError;
+use crate::storage::{DataKey, read, write};
+use crate::types::{CollectionInfo, FactoryStats};
+use soroban_sdk::{Address, Env, Vec};
+
+// Registry records live in persistent storage so the instance entry does not
+// grow with every deployed collection. Reads and writes keep them alive.
+
+pub fn collection_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::CollectionCount)
+        .unwrap_or(0)
+}
+
+/// Records a newly deployed collection under the next ID and indexes it by
+/// address and creator. Returns the assigned ID.
+pub fn add_collection(env: &Env, info: &CollectionInfo) -> u32 {
+    let id = collection_count(env);
+    write(env, DataKey::CollectionAddress(id), &info.address);
+    write(env, DataKey::CollectionId(info.address.clone()), &id);
+    write(env, DataKey::CollectionInfo(id), info);
+
+    let creator_len = creator_collection_len(env, &info.creator);
+    write(
+        env,
+        DataKey::CreatorCollectionAt(info.creator.clone(), creator_len),
+        &id,
+    );
+    write(
+        env,
+        DataKey::CreatorCollectionLen(info.creator.clone()),
+        &(creator_len + 1),
+    );
+
+    env.storage()
+        .instance()
+        .set(&DataKey::CollectionCount, &(id + 1));
+    id
+}
+
+pub fn get_info(env: &Env, id: u32) -> Option<CollectionInfo> {
+    read(env, DataKey::CollectionInfo(id))
+}
+
+pub fn set_info(env: &Env, id: u32, info: &CollectionInfo) {
+    write(env, DataKey::CollectionInfo(id), info);
+}
+
+pub fn get_address(env: &Env, id: u32) -> Option<Address> {
+    read(env, DataKey::CollectionAddress(id))
+}
+
+pub fn get_id(env: &Env, collection: Address) -> Option<u32> {
+    read(env, DataKey::CollectionId(collection))
+}
+
+/// Lifetime number of collections deployed by `creator`. Unlike the creator
+/// limit counter, this is never reset.
+pub fn creator_collection_len(env: &Env, creator: &Address) -> u32 {
+    read(env, DataKey::CreatorCollectionLen(creator.clone())).unwrap_or(0)
+}
+
+/// Collection addresses with IDs in `offset..offset + limit`.
+pub fn list(env: &Env, offset: u32, limit: u32) -> Vec<Address> {
+    let end = offset.saturating_add(limit).min(collection_count(env));
+    let mut page = Vec::new(env);
+    for id in offset..end {
+        if let Some(address) = get_address(env, id) {
+            page.push_back(address);
+        }
+    }
+    page
+}
+
+/// `creator`'s collection addresses in deployment order, from `offset`.
+pub fn list_by_creator(env: &Env, creator: &Address, offset: u32, limit: u32) -> Vec<Address> {
+    let end = offset
+        .saturating_add(limit)
+        .min(creator_collection_len(env, creator));
+    let mut page = Vec::new(env);
+    for index in offset..end {
+        let id: Option<u32> = read(env, DataKey::CreatorCollectionAt(creator.clone(), index));
+        if let Some(address) = id.and_then(|id| get_address(env, id)) {
+            page.push_back(address);
+        }
+    }
+    page
+}
+
+pub fn stats(env: &Env) -> FactoryStats {
+    env.storage()
+        .instance()
+        .get(&DataKey::FactoryStats)
+        .unwrap_or_default()
+}
+
+/// Applies a collection's reported mint/burn deltas to its record and the
+/// factory-wide totals.
+pub fn record_activity(
+    env: &Env,
+    collection: Address,
+    minted: u32,
+    burned: u32,
+) -> Result<(), ContractError> {
+    let id = get_id(env, collection).ok_or(ContractError::CollectionNotFound)?;
+    let mut info = get_info(env, id).ok_or(ContractError::CollectionNotFound)?;
+    info.total_tokens = info
+        .total_tokens
+        .checked_add(minted)
+        .and_then(|total| total.checked_sub(burned))
+        .ok_or(ContractError::InvalidAmount)?;
+    info.total_mints = info
+        .total_mints
+        .checked_add(minted)
+        .ok_or(ContractError::InvalidAmount)?;
+    info.total_burns = info
+        .total_burns
+        .checked_add(burned)
+        .ok_or(ContractError::InvalidAmount)?;
+    info.last_activity = env.ledger().timestamp();
+    set_info(env, id, &info);
+
+    let mut totals = stats(env);
+    totals.total_tokens = totals
+        .total_tokens
+        .checked_add(minted as u64)
+        .and_then(|total| total.checked_sub(burned as u64))
+        .ok_or(ContractError::InvalidAmount)?;
+    totals.total_mints = totals
+        .total_mints
+        .checked_add(minted as u64)
+        .ok_or(ContractError::InvalidAmount)?;
+    totals.total_burns = totals
+        .total_burns
+        .checked_add(burned as u64)
+        .ok_or(ContractError::InvalidAmount)?;
+    env.storage()
+        .instance()
+        .set(&DataKey::FactoryStats, &totals);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, contracttype};
+use soroban_sdk::{Address, Env, IntoVal, String, TryFromVal, Val, contracttype};
 
 #[derive(Clone)]
 #[contracttype]
@@ -7,12 +7,24 @@ pub enum DataKey {
     FactoryAdmin,
     FactoryFee,
     FeeAsset,
+    FeeAssets,
+    CreationFee(Address),
+    ReferralShare,
+    FeesCollected(Address),
+    ReferralEarnings(Address, Address),
     CollectionCount,
     CollectionAddress(u32),
     CollectionInfo(u32),
+    CollectionId(Address),
+    CreatorCollectionLen(Address),
+    CreatorCollectionAt(Address, u32),
+    FactoryStats,
+    CreatorTier(Address),
+    TierConfig(u32),
 
     MaxCollectionsPerCreator,
     CreatorCollectionCount(Address),
+    Template(String),
 
     // Collection Keys
     CollectionConfig,
@@ -25,4 +37,54 @@ pub enum DataKey {
     Minter(Address),
     Whitelist(Address),
     IsPaused,
+    Creator,
+    MintMode,
+    MintPrice,
+    WhitelistMinted(Address),
+    Proceeds(Address),
+    Approved(u32),
+    OperatorApproval(Address, Address),
+    TokenSupply(u32),
+    TokenMaxSupply(u32),
+    // Kept out of `CollectionConfig` so configs stored before it still decode
+    SemiFungible,
+    NextTokenId,
+}
+
+pub const MAX_TEMPLATE_VERSIONS: u32 = 20;
+/// Tier every creator starts in ("unverified").
+pub const DEFAULT_TIER: u32 = 0;
+pub const MAX_UPGRADE_BATCH: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 50;
+pub const MAX_FEE_ASSETS: u32 = 10;
+pub const MAX_WHITELIST_BATCH: u32 = 100;
+pub const MAX_TOKEN_BATCH: u32 = 50;
+/// Longest URI `mint_next` builds from `base_uri` and a token ID.
+pub const MAX_DERIVED_URI_LEN: usize = 256;
+
+// Persistent registry TTL, in ledgers (~5s each): extend to ~120 days once
+// fewer than ~30 days remain.
+pub const REGISTRY_TTL_THRESHOLD: u32 = 518_400;
+pub const REGISTRY_TTL_EXTEND: u32 = 2_073_600;
+
+// Per-collection, per-creator and per-holder records that would otherwise grow
+// the instance entry go through these, so reads and writes keep them alive.
+
+pub(crate) fn bump(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, REGISTRY_TTL_THRESHOLD, REGISTRY_TTL_EXTEND);
+}
+
+pub(crate) fn read<V: TryFromVal<Env, Val>>(env: &Env, key: DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        bump(env, &key);
+    }
+    value
+}
+
+pub(crate) fn write<V: IntoVal<Env, Val>>(env: &Env, key: DataKey, value: &V) {
+    env.storage().persistent().set(&key, value);
+    bump(env, &key);
 }
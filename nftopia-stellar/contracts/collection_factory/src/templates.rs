@@ -0,0 +1,96 @@
+use crate::error::ContractError;
+use crate::storage::{DataKey, MAX_TEMPLATE_VERSIONS};
+use crate::types::{SemVer, Template, TemplateVersion};
+use soroban_sdk::{BytesN, Env, String, Vec};
+
+pub fn get_template(env: &Env, template_id: &String) -> Option<Template> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Template(template_id.clone()))
+}
+
+/// Registers `wasm_hash` as a new version of `template_id`, creating the
+/// template on first use. Versions must be strictly increasing.
+pub fn register(
+    env: &Env,
+    template_id: String,
+    version: SemVer,
+    wasm_hash: BytesN<32>,
+) -> Result<(), ContractError> {
+    if template_id.is_empty() {
+        return Err(ContractError::InvalidAmount);
+    }
+    let mut template = get_template(env, &template_id).unwrap_or(Template {
+        id: template_id.clone(),
+        versions: Vec::new(env),
+    });
+    if let Some(latest) = template.versions.last()
+        && version <= latest.version
+    {
+        return Err(ContractError::InvalidVersion);
+    }
+    if template.versions.len() >= MAX_TEMPLATE_VERSIONS {
+        return Err(ContractError::InvalidVersion);
+    }
+
+    template.versions.push_back(TemplateVersion {
+        version,
+        wasm_hash,
+        deprecated: false,
+        registered_at: env.ledger().timestamp(),
+    });
+    env.storage()
+        .instance()
+        .set(&DataKey::Template(template_id), &template);
+    Ok(())
+}
+
+pub fn set_deprecated(
+    env: &Env,
+    template_id: String,
+    version: SemVer,
+    deprecated: bool,
+) -> Result<(), ContractError> {
+    let mut template = get_template(env, &template_id).ok_or(ContractError::TemplateNotFound)?;
+    let index = template
+        .versions
+        .iter()
+        .position(|v| v.version == version)
+        .ok_or(ContractError::TemplateNotFound)? as u32;
+    let mut entry = template.versions.get(index).unwrap();
+    entry.deprecated = deprecated;
+    template.versions.set(index, entry);
+    env.storage()
+        .instance()
+        .set(&DataKey::Template(template_id), &template);
+    Ok(())
+}
+
+/// A specific, non-deprecated version of `template_id`.
+pub fn active_version(
+    env: &Env,
+    template_id: &String,
+    version: &SemVer,
+) -> Result<TemplateVersion, ContractError> {
+    let template = get_template(env, template_id).ok_or(ContractError::TemplateNotFound)?;
+    let entry = template
+        .versions
+        .iter()
+        .find(|v| &v.version == version)
+        .ok_or(ContractError::TemplateNotFound)?;
+    if entry.deprecated {
+        return Err(ContractError::TemplateDeprecated);
+    }
+    Ok(entry)
+}
+
+/// Newest version of `template_id` that is not deprecated.
+pub fn latest_active(env: &Env, template_id: &String) -> Result<TemplateVersion, ContractError> {
+    let template = get_template(env, template_id).ok_or(ContractError::TemplateNotFound)?;
+    template
+        .versions
+        .iter()
+        .rev()
+        .find(|v| !v.deprecated)
+        .ok_or(ContractError::TemplateDeprecated)
+}
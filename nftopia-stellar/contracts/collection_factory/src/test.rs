@@ -1,9 +1,17 @@
 use crate::collection::{NftCollection, NftCollectionClient};
+use crate::error::ContractError;
 use crate::factory::{CollectionFactory, CollectionFactoryClient};
-use crate::types::CollectionConfig;
+use crate::fees;
+use crate::registry;
+use crate::storage::{DataKey, MAX_PAGE_SIZE, REGISTRY_TTL_THRESHOLD};
+use crate::templates;
+use crate::types::{CollectionConfig, CollectionInfo, FactoryStats, MintMode, SemVer, TierConfig};
 use soroban_sdk::TryFromVal;
-use soroban_sdk::testutils::Events;
-use soroban_sdk::{Address, Env, String, Symbol, Vec, symbol_short, testutils::Address as _};
+use soroban_sdk::testutils::{Events, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{
+    Address, BytesN, Env, String, Symbol, Vec, symbol_short, testutils::Address as _,
+};
 
 #[test]
 fn test_factory_logic() {
@@ -54,7 +62,7 @@ fn test_collection_logic() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 
     // Mint NFT
     let token_id = 1;
@@ -112,7 +120,7 @@ fn test_unauthorized_mint() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 
     // Try to mint from non-minter address
     // collection_client.mint(&user, &1, &String::from_str(&env, "uri"), &Vec::new(&env));
@@ -148,7 +156,7 @@ fn test_batch_minting() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 
     // Mint multiple tokens to user1 and user2
 
@@ -205,7 +213,7 @@ fn test_burn_authorized() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 
     // Mint a token to user1
     let token_id = 1u32;
@@ -251,7 +259,7 @@ fn test_access_control_roles() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 
     // Only admin is minter by default
     let token_id = 1u32;
@@ -297,7 +305,7 @@ fn test_event_emissions_happy() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://event1");
     let attributes = Vec::new(&env);
@@ -348,7 +356,7 @@ fn test_storage_state_happy() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     // Before mint
     assert_eq!(collection_client.total_supply(), 0);
     let token_id = 1u32;
@@ -395,7 +403,7 @@ fn test_invalid_params_empty_name() {
         royalty_recipient: admin.clone(),
     };
     // Should panic
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 }
 
 #[test]
@@ -420,7 +428,7 @@ fn test_invalid_params_excessive_royalty() {
         royalty_recipient: admin.clone(),
     };
     // Should panic
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 }
 
 #[test]
@@ -445,7 +453,7 @@ fn test_invalid_params_zero_max_supply() {
         royalty_recipient: admin.clone(),
     };
     // Should panic
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
 }
 
 #[test]
@@ -467,7 +475,7 @@ fn test_unauthorized_non_admin_mint() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://nonadmin1");
     let attributes = Vec::new(&env);
@@ -495,7 +503,7 @@ fn test_unauthorized_non_owner_burn() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://nonowner1");
     let attributes = Vec::new(&env);
@@ -516,7 +524,7 @@ fn test_unauthorized_fee_withdrawal() {
     let factory_client = CollectionFactoryClient::new(&env, &factory_id);
     factory_client.initialize(&admin, &fee_asset);
     // Should panic
-    factory_client.withdraw_fees(&user);
+    factory_client.withdraw_fees(&user, &fee_asset, &1);
 }
 
 #[test]
@@ -538,7 +546,7 @@ fn test_mint_beyond_max_supply() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://max1");
     let attributes = Vec::new(&env);
@@ -567,7 +575,7 @@ fn test_transfer_nonexistent_token() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     // Should panic
     collection_client.transfer(&user1, &user2, &42u32);
 }
@@ -591,7 +599,7 @@ fn test_duplicate_operations() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://dup1");
     let attributes = Vec::new(&env);
@@ -639,7 +647,7 @@ fn test_edge_unicode_metadata() {
         royalty_percentage: 100,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://ユニコード");
     let attributes = Vec::new(&env);
@@ -667,7 +675,7 @@ fn test_edge_boundary_values() {
         royalty_percentage: 10000,
         royalty_recipient: admin.clone(),
     };
-    collection_client.init(&admin, &admin.clone(), &config);
+    collection_client.init(&admin, &admin.clone(), &admin, &config);
     let token_id = 1u32;
     let uri = String::from_str(&env, "ipfs://boundary1");
     let attributes = Vec::new(&env);
@@ -706,7 +714,7 @@ fn test_factory_ownership_validation() {
     let collection_id = env.register(NftCollection, ());
     let collection_client = NftCollectionClient::new(&env, &collection_id);
 
-    collection_client.init(&admin, &factory_id.clone(), &config);
+    collection_client.init(&admin, &factory_id.clone(), &admin, &config);
 
     // Test get_factory returns the factory address
     assert_eq!(collection_client.get_factory(), Some(factory_id.clone()));
@@ -746,7 +754,7 @@ fn test_factory_verify_origin() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client.init(&admin, &factory_id.clone(), &config);
+    collection_client.init(&admin, &factory_id.clone(), &admin, &config);
 
     // Test verify_factory_origin from factory
     assert!(factory_client.verify_factory_origin(&collection_id.clone()));
@@ -785,7 +793,7 @@ fn test_get_collections_by_factory() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client1.init(&admin, &factory_id.clone(), &config1);
+    collection_client1.init(&admin, &factory_id.clone(), &admin, &config1);
 
     let collection_id2 = env.register(NftCollection, ());
     let collection_client2 = NftCollectionClient::new(&env, &collection_id2);
@@ -801,7 +809,7 @@ fn test_get_collections_by_factory() {
         royalty_recipient: admin.clone(),
     };
 
-    collection_client2.init(&admin, &factory_id.clone(), &config2);
+    collection_client2.init(&admin, &factory_id.clone(), &admin, &config2);
 
     // Note: get_collections_by_factory returns collections deployed by the factory
     // Since we're not using the actual create_collection flow, this test is limited
@@ -809,3 +817,1099 @@ fn test_get_collections_by_factory() {
     let collections = factory_client.get_collections_by_factory();
     assert_eq!(collections.len(), 0); // No collections deployed through factory in this test
 }
+
+// ------------------- TEMPLATE REGISTRY TESTS -------------------
+
+fn semver(major: u32, minor: u32, patch: u32) -> SemVer {
+    SemVer {
+        major,
+        minor,
+        patch,
+    }
+}
+
+fn test_config(env: &Env, admin: &Address) -> CollectionConfig {
+    CollectionConfig {
+        name: String::from_str(env, "Template NFT"),
+        symbol: String::from_str(env, "TMPL"),
+        description: String::from_str(env, "Template Test"),
+        base_uri: String::from_str(env, "https://template.com/"),
+        max_supply: Some(10),
+        is_public_mint: false,
+        royalty_percentage: 100,
+        royalty_recipient: admin.clone(),
+    }
+}
+
+#[test]
+fn test_template_versions_and_deprecation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let basic = String::from_str(&env, "basic-721");
+    factory_client.register_template(
+        &basic,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    factory_client.register_template(
+        &basic,
+        &semver(1, 2, 0),
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+
+    // Versions must strictly increase
+    let result = factory_client.try_register_template(
+        &basic,
+        &semver(1, 1, 9),
+        &BytesN::from_array(&env, &[3; 32]),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidVersion)));
+
+    let template = factory_client.get_template(&basic).unwrap();
+    assert_eq!(template.versions.len(), 2);
+
+    // Deprecating the newest falls back to the previous version
+    factory_client.set_template_deprecated(&basic, &semver(1, 2, 0), &true);
+    let active = env.as_contract(&factory_id, || {
+        templates::latest_active(&env, &basic).unwrap()
+    });
+    assert_eq!(active.version, semver(1, 0, 0));
+    assert_eq!(active.wasm_hash, BytesN::from_array(&env, &[1; 32]));
+
+    let result = factory_client.try_set_template_deprecated(&basic, &semver(9, 9, 9), &true);
+    assert_eq!(result, Err(Ok(ContractError::TemplateNotFound)));
+}
+
+#[test]
+fn test_create_collection_requires_active_template() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let salt = BytesN::from_array(&env, &[0; 32]);
+    let editions = String::from_str(&env, "editions");
+    let result = factory_client.try_create_collection(
+        &creator,
+        &editions,
+        &salt,
+        &test_config(&env, &admin),
+        &None,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(ContractError::TemplateNotFound)));
+
+    factory_client.register_template(
+        &editions,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    factory_client.set_template_deprecated(&editions, &semver(1, 0, 0), &true);
+    let result = factory_client.try_create_collection(
+        &creator,
+        &editions,
+        &salt,
+        &test_config(&env, &admin),
+        &None,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(ContractError::TemplateDeprecated)));
+    assert_eq!(factory_client.get_collection_count(), 0);
+}
+
+// ------------------- COLLECTION UPGRADE TESTS -------------------
+
+/// Records `count` collections of "basic-721" v1.0.0 in the factory registry
+/// without deploying wasm, so upgrade bookkeeping can be exercised natively.
+fn seed_collections(env: &Env, factory_id: &Address, creator: &Address, count: u32) {
+    let template_id = String::from_str(env, "basic-721");
+    env.as_contract(factory_id, || {
+        for _ in 0..count {
+            let info = CollectionInfo {
+                address: env.register(NftCollection, ()),
+                creator: creator.clone(),
+                config: test_config(env, creator),
+                created_at: 0,
+                total_tokens: 0,
+                template_id: template_id.clone(),
+                template_version: semver(1, 0, 0),
+                upgrade_opt_out: false,
+                total_mints: 0,
+                total_burns: 0,
+                last_activity: 0,
+                creator_tier: 0,
+            };
+            registry::add_collection(env, &info);
+        }
+    });
+}
+
+#[test]
+fn test_upgrade_rejects_opted_out_and_stale_versions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+    seed_collections(&env, &factory_id, &creator, 1);
+
+    let basic = String::from_str(&env, "basic-721");
+    factory_client.register_template(
+        &basic,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    factory_client.register_template(
+        &basic,
+        &semver(1, 1, 0),
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+
+    // Only the creator may opt out
+    let stranger = Address::generate(&env);
+    let result = factory_client.try_set_upgrade_opt_out(&stranger, &0, &true);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    factory_client.set_upgrade_opt_out(&creator, &0, &true);
+    assert!(
+        factory_client
+            .get_collection_info(&0)
+            .unwrap()
+            .upgrade_opt_out
+    );
+    let result = factory_client.try_upgrade_collection(&0, &semver(1, 1, 0));
+    assert_eq!(result, Err(Ok(ContractError::UpgradeOptedOut)));
+
+    // Opting back in still refuses downgrades and unknown versions
+    factory_client.set_upgrade_opt_out(&creator, &0, &false);
+    let result = factory_client.try_upgrade_collection(&0, &semver(1, 0, 0));
+    assert_eq!(result, Err(Ok(ContractError::InvalidVersion)));
+    let result = factory_client.try_upgrade_collection(&0, &semver(2, 0, 0));
+    assert_eq!(result, Err(Ok(ContractError::TemplateNotFound)));
+    let result = factory_client.try_upgrade_collection(&7, &semver(1, 1, 0));
+    assert_eq!(result, Err(Ok(ContractError::CollectionNotFound)));
+
+    factory_client.set_template_deprecated(&basic, &semver(1, 1, 0), &true);
+    let result = factory_client.try_upgrade_collection(&0, &semver(1, 1, 0));
+    assert_eq!(result, Err(Ok(ContractError::TemplateDeprecated)));
+}
+
+#[test]
+fn test_batch_upgrade_pages_and_skips_ineligible() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+    seed_collections(&env, &factory_id, &creator, 3);
+
+    let basic = String::from_str(&env, "basic-721");
+    factory_client.register_template(
+        &basic,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    for id in 0..3 {
+        factory_client.set_upgrade_opt_out(&creator, &id, &true);
+    }
+
+    // Every collection is opted out, so pages advance without upgrading
+    let next = factory_client.upgrade_collections(&basic, &semver(1, 0, 0), &0, &2);
+    assert_eq!(next, 2);
+    let next = factory_client.upgrade_collections(&basic, &semver(1, 0, 0), &next, &2);
+    assert_eq!(next, 3);
+    let next = factory_client.upgrade_collections(&basic, &semver(1, 0, 0), &next, &2);
+    assert_eq!(next, 3);
+
+    for id in 0..3 {
+        let info = factory_client.get_collection_info(&id).unwrap();
+        assert_eq!(info.template_version, semver(1, 0, 0));
+    }
+}
+
+// ------------------- REGISTRY QUERY TESTS -------------------
+
+#[test]
+fn test_registry_pagination_and_reverse_lookup() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    // IDs 0-2 belong to alice, 3-4 to bob, 5 to alice
+    seed_collections(&env, &factory_id, &alice, 3);
+    seed_collections(&env, &factory_id, &bob, 2);
+    seed_collections(&env, &factory_id, &alice, 1);
+    assert_eq!(factory_client.get_collection_count(), 6);
+
+    let first = factory_client.list_collections(&0, &4);
+    let rest = factory_client.list_collections(&4, &4);
+    assert_eq!(first.len(), 4);
+    assert_eq!(rest.len(), 2);
+    assert_eq!(factory_client.list_collections(&6, &4).len(), 0);
+    assert_eq!(factory_client.get_collections_by_factory().len(), 6);
+
+    // Page contents follow ID order and round-trip through the reverse lookup
+    for (index, address) in first.iter().chain(rest.iter()).enumerate() {
+        assert_eq!(
+            factory_client.get_collection_id(&address),
+            Some(index as u32)
+        );
+        assert_eq!(
+            factory_client.get_collection_address(&(index as u32)),
+            Some(address)
+        );
+    }
+    assert_eq!(
+        factory_client.get_collection_id(&Address::generate(&env)),
+        None
+    );
+
+    let alice_page = factory_client.list_collections_by_creator(&alice, &2, &10);
+    assert_eq!(alice_page.len(), 2);
+    assert_eq!(alice_page.get(0), factory_client.get_collection_address(&2));
+    assert_eq!(alice_page.get(1), factory_client.get_collection_address(&5));
+    let bob_page = factory_client.list_collections_by_creator(&bob, &0, &10);
+    assert_eq!(bob_page.len(), 2);
+    assert_eq!(bob_page.get(0), factory_client.get_collection_address(&3));
+
+    // Oversized requests are cut to one page
+    seed_collections(&env, &factory_id, &bob, MAX_PAGE_SIZE);
+    let capped = factory_client.get_collections_by_factory();
+    assert_eq!(capped.len(), MAX_PAGE_SIZE);
+    assert_eq!(
+        factory_client.list_collections(&0, &u32::MAX).len(),
+        MAX_PAGE_SIZE
+    );
+}
+
+#[test]
+fn test_registry_records_are_persistent() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+    seed_collections(&env, &factory_id, &creator, 1);
+    factory_client.reset_creator_collection_count(&creator);
+
+    env.as_contract(&factory_id, || {
+        assert!(!env.storage().instance().has(&DataKey::CollectionInfo(0)));
+        assert!(env.storage().persistent().has(&DataKey::CollectionInfo(0)));
+        assert!(
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::CollectionInfo(0))
+                >= REGISTRY_TTL_THRESHOLD
+        );
+        let count_key = DataKey::CreatorCollectionCount(creator.clone());
+        assert!(!env.storage().instance().has(&count_key));
+        assert!(env.storage().persistent().get_ttl(&count_key) >= REGISTRY_TTL_THRESHOLD);
+    });
+    assert!(factory_client.get_collection_info(&0).is_some());
+    assert_eq!(factory_client.get_creator_collection_count(&creator), 0);
+}
+
+// ------------------- ACTIVITY SYNC TESTS -------------------
+
+#[test]
+fn test_collection_activity_updates_factory_totals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+    seed_collections(&env, &factory_id, &creator, 2);
+
+    let collection_id = factory_client.get_collection_address(&1).unwrap();
+    let collection_client = NftCollectionClient::new(&env, &collection_id);
+    collection_client.init(&admin, &factory_id, &admin, &test_config(&env, &admin));
+
+    env.ledger().set_timestamp(1_000);
+    let uri = String::from_str(&env, "ipfs://token");
+    for token_id in 0..3 {
+        collection_client.mint(&admin, &holder, &token_id, &uri, &Vec::new(&env));
+    }
+    env.ledger().set_timestamp(2_000);
+    collection_client.burn(&holder, &1);
+
+    let info = factory_client.get_collection_info(&1).unwrap();
+    assert_eq!(info.total_tokens, 2);
+    assert_eq!(info.total_mints, 3);
+    assert_eq!(info.total_burns, 1);
+    assert_eq!(info.last_activity, 2_000);
+    assert_eq!(
+        factory_client.get_collection_info(&0).unwrap().total_mints,
+        0
+    );
+
+    let stats = factory_client.get_factory_stats();
+    assert_eq!(
+        stats,
+        FactoryStats {
+            total_tokens: 2,
+            total_mints: 3,
+            total_burns: 1,
+        }
+    );
+}
+
+#[test]
+fn test_report_activity_rejects_unregistered_collection() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let stray = env.register(NftCollection, ());
+    let result = factory_client.try_report_activity(&stray, &1, &0);
+    assert_eq!(result, Err(Ok(ContractError::CollectionNotFound)));
+
+    // The factory rejects the stray collection's reports, but its mints and
+    // burns still go through, uncounted
+    let stray_client = NftCollectionClient::new(&env, &stray);
+    stray_client.init(&admin, &factory_id, &admin, &test_config(&env, &admin));
+    stray_client.mint(
+        &admin,
+        &admin,
+        &0,
+        &String::from_str(&env, "ipfs://token"),
+        &Vec::new(&env),
+    );
+    assert_eq!(stray_client.owner_of(&0), Some(admin.clone()));
+    stray_client.burn(&admin, &0);
+    assert_eq!(factory_client.get_factory_stats(), FactoryStats::default());
+}
+
+// ------------------- MINT MODE TESTS -------------------
+
+fn setup_collection<'a>(env: &Env, admin: &Address, creator: &Address) -> NftCollectionClient<'a> {
+    let collection_id = env.register(NftCollection, ());
+    let collection_client = NftCollectionClient::new(env, &collection_id);
+    collection_client.init(admin, admin, creator, &test_config(env, admin));
+    collection_client
+}
+
+#[test]
+fn test_whitelist_mint_respects_quotas() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let collection_client = setup_collection(&env, &admin, &creator);
+    let uri = String::from_str(&env, "ipfs://token");
+    let attrs = Vec::new(&env);
+
+    // Minter-only by default, since the config is not public
+    assert_eq!(collection_client.mint_mode(), MintMode::MinterOnly);
+    let result = collection_client.try_mint_next(&alice, &alice);
+    assert_eq!(result, Err(Ok(ContractError::NotMinter)));
+
+    collection_client.set_mint_mode(&MintMode::Whitelist);
+    collection_client.set_whitelist(&soroban_sdk::vec![
+        &env,
+        (alice.clone(), 2),
+        (bob.clone(), 1)
+    ]);
+    assert_eq!(collection_client.whitelist_quota(&alice), 2);
+
+    // Whitelisted addresses cannot choose their own ID or URI
+    let result = collection_client.try_mint(&alice, &alice, &1, &uri, &attrs);
+    assert_eq!(result, Err(Ok(ContractError::NotMinter)));
+
+    // IDs come from the counter, stepping over one a minter already took
+    collection_client.mint(&admin, &bob, &2, &uri, &attrs);
+    assert_eq!(collection_client.mint_next(&alice, &alice), 1);
+    assert_eq!(collection_client.mint_next(&alice, &alice), 3);
+    assert_eq!(
+        collection_client.get_token_uri(&3),
+        Some(String::from_str(&env, "https://template.com/3"))
+    );
+    let result = collection_client.try_mint_next(&alice, &alice);
+    assert_eq!(result, Err(Ok(ContractError::MintQuotaExceeded)));
+    assert_eq!(collection_client.whitelist_minted(&alice), 2);
+
+    // Removing bob from the whitelist revokes his remaining quota
+    collection_client.set_whitelist(&soroban_sdk::vec![&env, (bob.clone(), 0)]);
+    let result = collection_client.try_mint_next(&bob, &bob);
+    assert_eq!(result, Err(Ok(ContractError::NotWhitelisted)));
+
+    // Designated minters bypass the whitelist
+    collection_client.mint(&admin, &bob, &4, &uri, &attrs);
+    assert_eq!(collection_client.total_supply(), 4);
+}
+
+#[test]
+fn test_whitelist_records_are_persistent() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let collection_client = setup_collection(&env, &admin, &creator);
+
+    collection_client.set_mint_mode(&MintMode::Whitelist);
+    collection_client.set_whitelist(&soroban_sdk::vec![&env, (alice.clone(), 2)]);
+    collection_client.mint_next(&alice, &alice);
+
+    env.as_contract(&collection_client.address, || {
+        for key in [
+            DataKey::Whitelist(alice.clone()),
+            DataKey::WhitelistMinted(alice.clone()),
+        ] {
+            assert!(!env.storage().instance().has(&key));
+            assert!(env.storage().persistent().get_ttl(&key) >= REGISTRY_TTL_THRESHOLD);
+        }
+    });
+}
+
+#[test]
+fn test_public_mint_charges_price_and_creator_withdraws() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let collection_client = setup_collection(&env, &admin, &creator);
+
+    let asset = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &asset).mint(&buyer, &1_000);
+    let asset_client = TokenClient::new(&env, &asset);
+
+    collection_client.set_mint_mode(&MintMode::Public);
+    let result = collection_client.try_set_mint_price(&asset, &-1);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    collection_client.set_mint_price(&asset, &300);
+
+    assert_eq!(collection_client.mint_next(&buyer, &buyer), 1);
+    assert_eq!(collection_client.mint_next(&buyer, &buyer), 2);
+    assert_eq!(collection_client.owner_of(&2), Some(buyer.clone()));
+    assert_eq!(asset_client.balance(&buyer), 400);
+    assert_eq!(collection_client.proceeds(&asset), 600);
+
+    assert_eq!(collection_client.withdraw_proceeds(&asset), 600);
+    assert_eq!(asset_client.balance(&creator), 600);
+    assert_eq!(collection_client.proceeds(&asset), 0);
+
+    // Clearing the price makes public mint free
+    collection_client.set_mint_price(&asset, &0);
+    assert_eq!(collection_client.mint_price(), None);
+    collection_client.mint_next(&buyer, &buyer);
+    assert_eq!(asset_client.balance(&buyer), 400);
+}
+
+// ------------------- APPROVAL TESTS -------------------
+
+#[test]
+fn test_approved_transfer_from_clears_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let market = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let collection_client = setup_collection(&env, &admin, &admin);
+    let uri = String::from_str(&env, "ipfs://token");
+    collection_client.mint(&admin, &owner, &1, &uri, &Vec::new(&env));
+
+    // Only the owner may approve, and unapproved spenders are refused
+    let result = collection_client.try_approve(&market, &market, &1);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    let result = collection_client.try_transfer_from(&market, &owner, &buyer, &1);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    collection_client.approve(&owner, &market, &1);
+    assert_eq!(collection_client.get_approved(&1), Some(market.clone()));
+
+    collection_client.transfer_from(&market, &owner, &buyer, &1);
+    assert_eq!(collection_client.owner_of(&1), Some(buyer.clone()));
+    assert_eq!(collection_client.get_approved(&1), None);
+
+    // The cleared approval cannot be reused against the new owner
+    let result = collection_client.try_transfer_from(&market, &buyer, &owner, &1);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
+#[test]
+fn test_operator_approval_and_burn_clears_approval() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let collection_client = setup_collection(&env, &admin, &admin);
+    let uri = String::from_str(&env, "ipfs://token");
+    collection_client.mint(&admin, &owner, &1, &uri, &Vec::new(&env));
+    collection_client.mint(&admin, &owner, &2, &uri, &Vec::new(&env));
+
+    collection_client.set_approval_for_all(&owner, &operator, &true);
+    assert!(collection_client.is_approved_for_all(&owner, &operator));
+    collection_client.transfer_from(&operator, &owner, &operator, &1);
+    assert_eq!(collection_client.owner_of(&1), Some(operator.clone()));
+
+    // Approvals live in persistent storage, kept alive on every read
+    let operator_key = DataKey::OperatorApproval(owner.clone(), operator.clone());
+    env.as_contract(&collection_client.address, || {
+        assert!(!env.storage().instance().has(&operator_key));
+        assert!(env.storage().persistent().get_ttl(&operator_key) >= REGISTRY_TTL_THRESHOLD);
+    });
+
+    collection_client.set_approval_for_all(&owner, &operator, &false);
+    let result = collection_client.try_transfer_from(&operator, &owner, &operator, &2);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    env.as_contract(&collection_client.address, || {
+        assert!(!env.storage().persistent().has(&operator_key));
+    });
+
+    collection_client.approve(&owner, &spender, &2);
+    env.as_contract(&collection_client.address, || {
+        let key = DataKey::Approved(2);
+        assert!(!env.storage().instance().has(&key));
+        assert!(env.storage().persistent().get_ttl(&key) >= REGISTRY_TTL_THRESHOLD);
+    });
+    collection_client.burn(&owner, &2);
+    assert_eq!(collection_client.get_approved(&2), None);
+}
+
+// ------------------- SEMI-FUNGIBLE TESTS -------------------
+
+fn setup_semi_fungible<'a>(env: &Env, admin: &Address) -> NftCollectionClient<'a> {
+    let collection_id = env.register(NftCollection, ());
+    let collection_client = NftCollectionClient::new(env, &collection_id);
+    let mut config = test_config(env, admin);
+    config.max_supply = Some(1_000);
+    collection_client.init(admin, admin, admin, &config);
+    collection_client.set_semi_fungible(&true);
+    collection_client
+}
+
+#[test]
+fn test_semi_fungible_mint_and_per_id_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let collection_client = setup_semi_fungible(&env, &admin);
+    let uri = String::from_str(&env, "ipfs://potion");
+    let attrs = Vec::new(&env);
+
+    collection_client.set_token_max_supply(&7, &100);
+    collection_client.mint_quantity(&admin, &alice, &7, &60, &uri, &attrs);
+    collection_client.mint_quantity(&admin, &bob, &7, &40, &uri, &attrs);
+    assert_eq!(collection_client.balance_of(&alice, &7), 60);
+    assert_eq!(collection_client.balance_of(&bob, &7), 40);
+    assert_eq!(collection_client.token_supply(&7), 100);
+    assert_eq!(collection_client.total_supply(), 100);
+    assert_eq!(collection_client.owner_of(&7), None);
+
+    let result = collection_client.try_mint_quantity(&admin, &bob, &7, &1, &uri, &attrs);
+    assert_eq!(result, Err(Ok(ContractError::SupplyLimitExceeded)));
+    let result = collection_client.try_set_token_max_supply(&7, &99);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+
+    // Single-token entrypoints are closed in semi-fungible mode
+    let result = collection_client.try_mint(&admin, &alice, &8, &uri, &attrs);
+    assert_eq!(result, Err(Ok(ContractError::WrongTokenMode)));
+
+    collection_client.burn_amount(&bob, &7, &15);
+    assert_eq!(collection_client.token_supply(&7), 85);
+    assert_eq!(collection_client.total_supply(), 85);
+    let result = collection_client.try_burn_amount(&bob, &7, &26);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+
+    // Public minters may add supply to an existing ID but not create one
+    collection_client.set_mint_mode(&MintMode::Public);
+    collection_client.mint_quantity(&admin, &bob, &8, &1, &uri, &attrs);
+    collection_client.mint_quantity(&alice, &alice, &8, &2, &uri, &attrs);
+    assert_eq!(collection_client.balance_of(&alice, &8), 2);
+    let result = collection_client.try_mint_quantity(&alice, &alice, &9, &1, &uri, &attrs);
+    assert_eq!(result, Err(Ok(ContractError::NotMinter)));
+}
+
+#[test]
+fn test_semi_fungible_transfers_and_batches() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let collection_client = setup_semi_fungible(&env, &admin);
+    let uri = String::from_str(&env, "ipfs://item");
+    let attrs = Vec::new(&env);
+    collection_client.mint_quantity(&admin, &alice, &1, &10, &uri, &attrs);
+    collection_client.mint_quantity(&admin, &alice, &2, &5, &uri, &attrs);
+
+    collection_client.transfer_amount(&alice, &alice, &bob, &1, &4);
+    let result = collection_client.try_transfer_amount(&alice, &alice, &bob, &1, &7);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+
+    // A failing item rolls back the whole batch
+    let result = collection_client.try_batch_transfer_amounts(
+        &alice,
+        &alice,
+        &bob,
+        &soroban_sdk::vec![&env, (1, 1), (2, 6)],
+    );
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+    collection_client.batch_transfer_amounts(
+        &alice,
+        &alice,
+        &bob,
+        &soroban_sdk::vec![&env, (1, 1), (2, 5)],
+    );
+
+    let balances = collection_client.balance_of_batch(
+        &soroban_sdk::vec![&env, alice.clone(), bob.clone(), alice.clone(), bob.clone()],
+        &soroban_sdk::vec![&env, 1, 1, 2, 2],
+    );
+    assert_eq!(balances, soroban_sdk::vec![&env, 5, 5, 0, 5]);
+
+    // Only operators approved for all may move someone else's units
+    let operator = Address::generate(&env);
+    let result = collection_client.try_transfer_amount(&operator, &bob, &operator, &1, &1);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    collection_client.set_approval_for_all(&bob, &operator, &true);
+    collection_client.transfer_amount(&operator, &bob, &operator, &1, &2);
+    collection_client.batch_transfer_amounts(
+        &operator,
+        &bob,
+        &alice,
+        &soroban_sdk::vec![&env, (1, 1), (2, 5)],
+    );
+    assert_eq!(collection_client.balance_of(&operator, &1), 2);
+    assert_eq!(collection_client.balance_of(&alice, &2), 5);
+
+    // Balances and per-ID supply are persistent; emptied balances are dropped
+    env.as_contract(&collection_client.address, || {
+        for key in [DataKey::Balance(alice.clone(), 1), DataKey::TokenSupply(1)] {
+            assert!(!env.storage().instance().has(&key));
+            assert!(env.storage().persistent().get_ttl(&key) >= REGISTRY_TTL_THRESHOLD);
+        }
+        assert!(
+            !env.storage()
+                .persistent()
+                .has(&DataKey::Balance(bob.clone(), 2))
+        );
+    });
+    let result = collection_client
+        .try_balance_of_batch(&soroban_sdk::vec![&env, alice.clone()], &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+}
+
+/// `CollectionConfig` as stored by collections deployed before quantity mode.
+#[soroban_sdk::contracttype]
+struct CollectionConfigV1 {
+    name: String,
+    symbol: String,
+    description: String,
+    base_uri: String,
+    max_supply: Option<u32>,
+    is_public_mint: bool,
+    royalty_percentage: u32,
+    royalty_recipient: Address,
+}
+
+#[test]
+fn test_upgraded_collection_keeps_stored_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let collection_id = env.register(NftCollection, ());
+    let collection_client = NftCollectionClient::new(&env, &collection_id);
+
+    // State written by the original `init`, now running under the new code
+    env.as_contract(&collection_id, || {
+        let store = env.storage().instance();
+        store.set(&DataKey::FactoryAdmin, &admin);
+        store.set(&DataKey::FactoryAddress, &admin);
+        store.set(
+            &DataKey::CollectionConfig,
+            &CollectionConfigV1 {
+                name: String::from_str(&env, "Legacy"),
+                symbol: String::from_str(&env, "OLD"),
+                description: String::from_str(&env, "Deployed before upgrade"),
+                base_uri: String::from_str(&env, "ipfs://legacy/"),
+                max_supply: Some(5),
+                is_public_mint: false,
+                royalty_percentage: 250,
+                royalty_recipient: admin.clone(),
+            },
+        );
+        store.set(&DataKey::TotalSupply, &1u32);
+        store.set(&DataKey::IsPaused, &false);
+        store.set(&DataKey::Creator, &admin);
+        store.set(&DataKey::Minter(admin.clone()), &true);
+        store.set(&DataKey::Owner(0), &owner);
+        store.set(&DataKey::Balance(owner.clone(), 0), &1u32);
+    });
+
+    assert!(!collection_client.is_semi_fungible());
+    let uri = String::from_str(&env, "ipfs://legacy/1");
+    collection_client.mint(&admin, &owner, &1, &uri, &Vec::new(&env));
+    assert_eq!(collection_client.owner_of(&1), Some(owner.clone()));
+    assert_eq!(collection_client.total_supply(), 2);
+
+    // Balances left in instance storage still count and move on first change
+    assert_eq!(collection_client.balance_of(&owner, &0), 1);
+    collection_client.transfer(&owner, &admin, &0);
+    assert_eq!(collection_client.balance_of(&owner, &0), 0);
+    assert_eq!(collection_client.balance_of(&admin, &0), 1);
+    env.as_contract(&collection_id, || {
+        assert!(
+            !env.storage()
+                .instance()
+                .has(&DataKey::Balance(owner.clone(), 0))
+        );
+    });
+
+    // The token mode is fixed while tokens are outstanding
+    let result = collection_client.try_set_semi_fungible(&true);
+    assert_eq!(result, Err(Ok(ContractError::WrongTokenMode)));
+    collection_client.burn(&owner, &1);
+    collection_client.burn(&admin, &0);
+    collection_client.set_semi_fungible(&true);
+    collection_client.mint_quantity(&admin, &owner, &2, &3, &uri, &Vec::new(&env));
+    assert_eq!(collection_client.balance_of(&owner, &2), 3);
+}
+
+// ------------------- CREATOR TIER TESTS -------------------
+
+#[test]
+fn test_creator_tier_assignment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    // The default tier mirrors the global limit until configured
+    assert_eq!(factory_client.get_creator_tier(&creator), 0);
+    assert_eq!(
+        factory_client.get_tier_config(&0).unwrap().max_collections,
+        10
+    );
+    assert_eq!(factory_client.get_remaining_count(&creator), 10);
+
+    let result = factory_client.try_set_creator_tier(&creator, &1);
+    assert_eq!(result, Err(Ok(ContractError::TierNotFound)));
+
+    let mut verified = TierConfig {
+        max_collections: 50,
+        overflow_fee: 100,
+        allowed_templates: Vec::new(&env),
+        min_royalty: 600,
+        max_royalty: 500,
+    };
+    let result = factory_client.try_set_tier_config(&1, &verified);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoyalty)));
+    verified.min_royalty = 0;
+    factory_client.set_tier_config(&1, &verified);
+
+    factory_client.set_creator_tier(&creator, &1);
+    assert!(env.events().all().iter().any(|e| e.1.iter().any(|t| {
+        if let Ok(sym) = Symbol::try_from_val(&env, &t) {
+            sym == Symbol::new(&env, "creator_tier_changed")
+        } else {
+            false
+        }
+    })));
+    assert_eq!(factory_client.get_creator_tier(&creator), 1);
+    assert_eq!(factory_client.get_remaining_count(&creator), 50);
+
+    // Assignments are per-creator records; tier configs stay in the instance
+    env.as_contract(&factory_id, || {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let key = DataKey::CreatorTier(creator.clone());
+        assert!(!env.storage().instance().has(&key));
+        assert!(env.storage().persistent().get_ttl(&key) >= REGISTRY_TTL_THRESHOLD);
+        assert!(env.storage().instance().has(&DataKey::TierConfig(1)));
+    });
+}
+
+#[test]
+fn test_create_collection_enforces_tier_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let basic = String::from_str(&env, "basic-721");
+    let editions = String::from_str(&env, "editions");
+    factory_client.register_template(
+        &basic,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    factory_client.register_template(
+        &editions,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+
+    factory_client.set_tier_config(
+        &2,
+        &TierConfig {
+            max_collections: 0,
+            overflow_fee: 0,
+            allowed_templates: soroban_sdk::vec![&env, basic.clone()],
+            min_royalty: 0,
+            max_royalty: 500,
+        },
+    );
+    factory_client.set_creator_tier(&creator, &2);
+
+    let salt = BytesN::from_array(&env, &[0; 32]);
+    let mut config = test_config(&env, &admin);
+    let result =
+        factory_client.try_create_collection(&creator, &editions, &salt, &config, &None, &None);
+    assert_eq!(result, Err(Ok(ContractError::TemplateNotAllowed)));
+
+    config.royalty_percentage = 1_000;
+    let result =
+        factory_client.try_create_collection(&creator, &basic, &salt, &config, &None, &None);
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoyalty)));
+
+    // The tier's own limit applies, not the global one
+    config.royalty_percentage = 100;
+    let result =
+        factory_client.try_create_collection(&creator, &basic, &salt, &config, &None, &None);
+    assert_eq!(result, Err(Ok(ContractError::MaxCollectionsExceeded)));
+}
+
+// ------------------- ADDRESS PREDICTION TESTS -------------------
+
+#[test]
+fn test_predict_collection_address_is_deterministic() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let other = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let salt = BytesN::from_array(&env, &[7; 32]);
+    let predicted = factory_client.predict_collection_address(&creator, &salt);
+    assert_eq!(
+        predicted,
+        factory_client.predict_collection_address(&creator, &salt)
+    );
+    assert_ne!(
+        predicted,
+        factory_client.predict_collection_address(&creator, &BytesN::from_array(&env, &[8; 32]))
+    );
+    assert_ne!(
+        predicted,
+        factory_client.predict_collection_address(&other, &salt)
+    );
+}
+
+#[test]
+fn test_create_collection_rejects_used_salt() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let basic = String::from_str(&env, "basic-721");
+    factory_client.register_template(
+        &basic,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+
+    // Occupy the predicted address as if an earlier deploy used this salt
+    let salt = BytesN::from_array(&env, &[7; 32]);
+    let predicted = factory_client.predict_collection_address(&creator, &salt);
+    env.register_at(&predicted, NftCollection, ());
+
+    let result = factory_client.try_create_collection(
+        &creator,
+        &basic,
+        &salt,
+        &test_config(&env, &admin),
+        &None,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(ContractError::CollectionAlreadyExists)));
+    assert_eq!(factory_client.get_collection_count(), 0);
+}
+
+// ------------------- CREATION FEE TESTS -------------------
+
+#[test]
+fn test_creation_fee_assets_and_referral_share() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let other = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &usdc).mint(&creator, &10_000);
+    factory_client.set_creation_fee(&usdc, &1_000);
+    factory_client.set_creation_fee(&xlm, &50);
+    factory_client.set_referral_share(&2_000);
+    assert!(env.events().all().iter().any(|e| e.1.iter().any(|t| {
+        if let Ok(sym) = Symbol::try_from_val(&env, &t) {
+            sym == Symbol::new(&env, "referral_share_set")
+        } else {
+            false
+        }
+    })));
+    assert_eq!(factory_client.get_fee_assets().len(), 2);
+    assert_eq!(factory_client.get_creation_fee(&xlm), 50);
+    let result = factory_client.try_set_referral_share(&10_001);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+
+    // Creation must name an accepted asset once fees are configured
+    let basic = String::from_str(&env, "basic-721");
+    factory_client.register_template(
+        &basic,
+        &semver(1, 0, 0),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    let salt = BytesN::from_array(&env, &[0; 32]);
+    let config = test_config(&env, &admin);
+    let result =
+        factory_client.try_create_collection(&creator, &basic, &salt, &config, &None, &None);
+    assert_eq!(result, Err(Ok(ContractError::FeeAssetNotAccepted)));
+    let result =
+        factory_client.try_create_collection(&creator, &basic, &salt, &config, &Some(other), &None);
+    assert_eq!(result, Err(Ok(ContractError::FeeAssetNotAccepted)));
+    let result = factory_client.try_create_collection(
+        &creator,
+        &basic,
+        &salt,
+        &config,
+        &Some(usdc.clone()),
+        &Some(creator.clone()),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidRecipient)));
+
+    // Deployment needs real wasm, so charge the fee directly
+    env.as_contract(&factory_id, || {
+        fees::charge_creation_fee(&env, &creator, Some(usdc.clone()), Some(referrer.clone()))
+            .unwrap();
+        fees::charge_creation_fee(&env, &creator, Some(usdc.clone()), None).unwrap();
+    });
+    let usdc_client = TokenClient::new(&env, &usdc);
+    assert_eq!(usdc_client.balance(&creator), 8_000);
+    assert_eq!(usdc_client.balance(&referrer), 200);
+    assert_eq!(usdc_client.balance(&factory_id), 1_800);
+    assert_eq!(factory_client.get_fees_collected(&usdc), 1_800);
+    assert_eq!(factory_client.get_referral_earnings(&referrer, &usdc), 200);
+    env.as_contract(&factory_id, || {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        for key in [
+            DataKey::FeesCollected(usdc.clone()),
+            DataKey::ReferralEarnings(referrer.clone(), usdc.clone()),
+        ] {
+            assert!(!env.storage().instance().has(&key));
+            assert!(env.storage().persistent().get_ttl(&key) >= REGISTRY_TTL_THRESHOLD);
+        }
+    });
+
+    // A fee too large to split is refused rather than wrapping
+    factory_client.set_creation_fee(&xlm, &i128::MAX);
+    env.as_contract(&factory_id, || {
+        let result =
+            fees::charge_creation_fee(&env, &creator, Some(xlm.clone()), Some(referrer.clone()));
+        assert_eq!(result, Err(ContractError::InvalidAmount));
+    });
+
+    factory_client.set_creation_fee(&xlm, &0);
+    assert_eq!(
+        factory_client.get_fee_assets(),
+        soroban_sdk::vec![&env, usdc.clone()]
+    );
+}
+
+#[test]
+fn test_withdraw_fees_partial_and_per_asset() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let factory_id = env.register(CollectionFactory, ());
+    let factory_client = CollectionFactoryClient::new(&env, &factory_id);
+    factory_client.initialize(&admin, &admin);
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &usdc).mint(&factory_id, &800);
+    StellarAssetClient::new(&env, &xlm).mint(&factory_id, &50);
+
+    factory_client.withdraw_fees(&admin, &usdc, &300);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&admin), 300);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&factory_id), 500);
+
+    let result = factory_client.try_withdraw_fees(&admin, &xlm, &51);
+    assert_eq!(result, Err(Ok(ContractError::InsufficientBalance)));
+    let result = factory_client.try_withdraw_fees(&admin, &xlm, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    factory_client.withdraw_fees(&admin, &xlm, &50);
+    assert_eq!(TokenClient::new(&env, &xlm).balance(&admin), 50);
+}
@@ -0,0 +1,71 @@
+use crate::error::ContractError;
+use crate::storage::{DEFAULT_TIER, DataKey, read, write};
+use crate::types::TierConfig;
+use soroban_sdk::{Address, Env, String, Vec};
+
+/// Per-creator assignments are persistent; tier configs stay in instance storage.
+pub fn get_tier(env: &Env, creator: &Address) -> u32 {
+    read(env, DataKey::CreatorTier(creator.clone())).unwrap_or(DEFAULT_TIER)
+}
+
+/// Configured tier, or for an unconfigured default tier the legacy global
+/// limit and overflow fee with no template or royalty restrictions.
+pub fn get_config(env: &Env, tier: u32) -> Option<TierConfig> {
+    let config = env.storage().instance().get(&DataKey::TierConfig(tier));
+    if config.is_some() || tier != DEFAULT_TIER {
+        return config;
+    }
+    Some(TierConfig {
+        max_collections: env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxCollectionsPerCreator)
+            .unwrap_or(10),
+        overflow_fee: env
+            .storage()
+            .instance()
+            .get(&DataKey::FactoryFee)
+            .unwrap_or(0),
+        allowed_templates: Vec::new(env),
+        min_royalty: 0,
+        max_royalty: 10000,
+    })
+}
+
+pub fn set_config(env: &Env, tier: u32, config: &TierConfig) -> Result<(), ContractError> {
+    if config.min_royalty > config.max_royalty || config.max_royalty > 10000 {
+        return Err(ContractError::InvalidRoyalty);
+    }
+    if config.overflow_fee < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::TierConfig(tier), config);
+    Ok(())
+}
+
+/// Assigns `creator` to `tier`, returning the previous tier.
+pub fn set_tier(env: &Env, creator: &Address, tier: u32) -> Result<u32, ContractError> {
+    if get_config(env, tier).is_none() {
+        return Err(ContractError::TierNotFound);
+    }
+    let old_tier = get_tier(env, creator);
+    write(env, DataKey::CreatorTier(creator.clone()), &tier);
+    Ok(old_tier)
+}
+
+/// Checks a new collection's template and royalty against the tier's rules.
+pub fn check_collection(
+    config: &TierConfig,
+    template_id: &String,
+    royalty_percentage: u32,
+) -> Result<(), ContractError> {
+    if !config.allowed_templates.is_empty() && !config.allowed_templates.contains(template_id) {
+        return Err(ContractError::TemplateNotAllowed);
+    }
+    if royalty_percentage < config.min_royalty || royalty_percentage > config.max_royalty {
+        return Err(ContractError::InvalidRoyalty);
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, String, Vec, contracttype};
+use soroban_sdk::{Address, BytesN, String, Vec, contracttype};
 
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -31,6 +31,79 @@ pub struct CollectionInfo {
     pub config: CollectionConfig,
     pub created_at: u64,
     pub total_tokens: u32,
+    pub template_id: String,
+    pub template_version: SemVer,
+    pub upgrade_opt_out: bool,
+    pub total_mints: u32,
+    pub total_burns: u32,
+    pub last_activity: u64,
+    pub creator_tier: u32,
+}
+
+/// Limits and fees for creators in one verification tier.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TierConfig {
+    pub max_collections: u32,
+    /// Charged per collection beyond `max_collections`; zero blocks overflow.
+    pub overflow_fee: i128,
+    /// Templates creators in this tier may deploy; empty allows any.
+    pub allowed_templates: Vec<String>,
+    pub min_royalty: u32, // Basis points
+    pub max_royalty: u32, // Basis points
+}
+
+/// Token activity summed across every registered collection.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[contracttype]
+pub struct FactoryStats {
+    pub total_tokens: u64,
+    pub total_mints: u64,
+    pub total_burns: u64,
+}
+
+// Field order gives the derived ordering semver precedence
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[contracttype]
+pub struct SemVer {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TemplateVersion {
+    pub version: SemVer,
+    pub wasm_hash: BytesN<32>,
+    pub deprecated: bool,
+    pub registered_at: u64,
+}
+
+/// A named collection template (e.g. "basic-721") and its approved versions, oldest first.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Template {
+    pub id: String,
+    pub versions: Vec<TemplateVersion>,
+}
+
+/// Who besides designated minters may call `NftCollection::mint`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum MintMode {
+    MinterOnly,
+    /// Whitelisted addresses, each up to its quota.
+    Whitelist,
+    /// Anyone, paying the mint price if one is set.
+    Public,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MintPrice {
+    pub asset: Address,
+    pub amount: i128,
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,41 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::registry;
+use crate::types::{CollectionInfo, TemplateVersion};
+use soroban_sdk::{Env, IntoVal, Symbol, vec};
+
+/// Moves collection `id` to `target`, which must be a newer version of the
+/// template it was deployed from. Opted-out collections are refused.
+pub fn upgrade_collection(
+    env: &Env,
+    id: u32,
+    mut info: CollectionInfo,
+    target: &TemplateVersion,
+) -> Result<(), ContractError> {
+    if info.upgrade_opt_out {
+        return Err(ContractError::UpgradeOptedOut);
+    }
+    if target.version <= info.template_version {
+        return Err(ContractError::InvalidVersion);
+    }
+
+    // The factory is the collection's upgrade authority; the direct call
+    // satisfies the collection's `require_auth` on the factory address.
+    env.invoke_contract::<()>(
+        &info.address,
+        &Symbol::new(env, "upgrade"),
+        vec![env, target.wasm_hash.clone().into_val(env)],
+    );
+
+    let from_version = info.template_version.clone();
+    info.template_version = target.version.clone();
+    registry::set_info(env, id, &info);
+
+    events::emit_collection_upgraded(env, info.address, id, from_version, target.version.clone());
+    Ok(())
+}
+
+/// Whether a batch upgrade to `target` should touch this collection.
+pub fn is_eligible(info: &CollectionInfo, target: &TemplateVersion) -> bool {
+    !info.upgrade_opt_out && info.template_version < target.version
+}
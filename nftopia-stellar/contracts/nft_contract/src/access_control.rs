@@ -1,20 +1,25 @@
 use crate::error::ContractError;
+use crate::multisig;
 use crate::storage::DataKey;
-use crate::types::role;
+use crate::types::{admin_action, role};
 use soroban_sdk::{Address, Env, panic_with_error};
 
 pub fn grant_role(env: &Env, caller: &Address, target: &Address, role: u32) {
     require_owner(env, caller);
-    env.storage()
-        .instance()
-        .set(&DataKey::Role(target.clone(), role), &true);
+    multisig::require_unprotected(env, admin_action::GRANT_ROLE);
+    apply_role(env, target, role, true);
 }
 
 pub fn revoke_role(env: &Env, caller: &Address, target: &Address, role_disc: u32) {
     require_owner(env, caller);
+    multisig::require_unprotected(env, admin_action::REVOKE_ROLE);
+    apply_role(env, target, role_disc, false);
+}
+
+pub fn apply_role(env: &Env, target: &Address, role_disc: u32, granted: bool) {
     env.storage()
         .instance()
-        .set(&DataKey::Role(target.clone(), role_disc), &false);
+        .set(&DataKey::Role(target.clone(), role_disc), &granted);
 }
 
 pub fn has_role(env: &Env, address: &Address, role_disc: u32) -> bool {
@@ -66,6 +71,17 @@ pub fn require_metadata_updater(env: &Env, caller: &Address) -> Result<(), Contr
     }
 }
 
+pub fn require_admin_or_metadata_updater(env: &Env, caller: &Address) -> Result<(), ContractError> {
+    if has_role(env, caller, role::OWNER)
+        || has_role(env, caller, role::ADMIN)
+        || has_role(env, caller, role::METADATA_UPDATER)
+    {
+        Ok(())
+    } else {
+        Err(ContractError::NotAuthorized)
+    }
+}
+
 pub fn init_owner(env: &Env, owner: &Address) {
     env.storage()
         .instance()
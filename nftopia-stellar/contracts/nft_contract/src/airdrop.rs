@@ -0,0 +1,239 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{DataKey, MAX_BATCH_SIZE, MAX_MERKLE_PROOF_DEPTH, MAX_SUPPLY_HARD_CAP};
+use crate::token;
+use crate::types::{AirdropCampaign, AirdropLeaf, TokenAttribute};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec, xdr::ToXdr};
+
+const BITMAP_WORD_BITS: u32 = 128;
+
+pub fn hash_leaf(env: &Env, leaf: &AirdropLeaf) -> BytesN<32> {
+    env.crypto().sha256(&leaf.clone().to_xdr(env)).to_bytes()
+}
+
+pub fn hash_attributes(env: &Env, attributes: &Vec<TokenAttribute>) -> BytesN<32> {
+    env.crypto()
+        .sha256(&attributes.clone().to_xdr(env))
+        .to_bytes()
+}
+
+/// Verifies a proof built with sorted-pair hashing: each parent is
+/// `sha256(min(a, b) || max(a, b))`, so proofs carry no left/right flags.
+pub fn verify_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    leaf: BytesN<32>,
+    proof: &Vec<BytesN<32>>,
+) -> bool {
+    if proof.len() > MAX_MERKLE_PROOF_DEPTH {
+        return false;
+    }
+    let mut computed = leaf;
+    for node in proof.iter() {
+        let (first, second) = if computed <= node {
+            (computed, node)
+        } else {
+            (node, computed)
+        };
+        let mut buf = Bytes::from_array(env, &first.to_array());
+        buf.append(&Bytes::from_array(env, &second.to_array()));
+        computed = env.crypto().sha256(&buf).to_bytes();
+    }
+    &computed == root
+}
+
+fn get_campaign(env: &Env, campaign_id: u32) -> Result<AirdropCampaign, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Airdrop(campaign_id))
+        .ok_or(ContractError::NotFound)
+}
+
+pub fn is_claimed(env: &Env, campaign_id: u32, index: u32) -> bool {
+    let word: u128 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AirdropClaimed(
+            campaign_id,
+            index / BITMAP_WORD_BITS,
+        ))
+        .unwrap_or(0);
+    word & (1u128 << (index % BITMAP_WORD_BITS)) != 0
+}
+
+fn set_claimed(env: &Env, campaign_id: u32, index: u32) {
+    let key = DataKey::AirdropClaimed(campaign_id, index / BITMAP_WORD_BITS);
+    let word: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &(word | (1u128 << (index % BITMAP_WORD_BITS))));
+}
+
+pub fn create_airdrop(
+    env: &Env,
+    caller: &Address,
+    root: BytesN<32>,
+    leaf_count: u32,
+    expires_at: u64,
+) -> Result<u32, ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+    if leaf_count == 0 || leaf_count as u64 > MAX_SUPPLY_HARD_CAP {
+        return Err(ContractError::InvalidAmount);
+    }
+    if expires_at <= env.ledger().timestamp() {
+        return Err(ContractError::ClaimExpired);
+    }
+
+    let campaign_id: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::AirdropCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::AirdropCount, &(campaign_id + 1));
+
+    env.storage().persistent().set(
+        &DataKey::Airdrop(campaign_id),
+        &AirdropCampaign {
+            root: root.clone(),
+            creator: caller.clone(),
+            leaf_count,
+            claimed_count: 0,
+            expires_at,
+        },
+    );
+
+    events::emit_airdrop_created(env, campaign_id, root, leaf_count, expires_at);
+    Ok(campaign_id)
+}
+
+/// Validates `leaf` against the campaign, marks it claimed and mints it to `to`.
+fn redeem_leaf(
+    env: &Env,
+    campaign_id: u32,
+    campaign: &mut AirdropCampaign,
+    leaf: AirdropLeaf,
+    attributes: Vec<TokenAttribute>,
+    proof: &Vec<BytesN<32>>,
+    to: &Address,
+) -> Result<u64, ContractError> {
+    if leaf.index >= campaign.leaf_count {
+        return Err(ContractError::InvalidProof);
+    }
+    if is_claimed(env, campaign_id, leaf.index) {
+        return Err(ContractError::AlreadyClaimed);
+    }
+    if hash_attributes(env, &attributes) != leaf.attributes_hash {
+        return Err(ContractError::InvalidProof);
+    }
+    if !verify_proof(env, &campaign.root, hash_leaf(env, &leaf), proof) {
+        return Err(ContractError::InvalidProof);
+    }
+
+    set_claimed(env, campaign_id, leaf.index);
+    campaign.claimed_count += 1;
+
+    let token_id = token::mint_one(
+        env,
+        &campaign.creator,
+        to,
+        leaf.metadata_uri,
+        attributes,
+        None,
+    )?;
+    events::emit_airdrop_claimed(env, campaign_id, leaf.index, to.clone(), token_id);
+    Ok(token_id)
+}
+
+/// Mints the allocation described by `leaf` to its recipient.
+pub fn claim_airdrop(
+    env: &Env,
+    campaign_id: u32,
+    leaf: AirdropLeaf,
+    attributes: Vec<TokenAttribute>,
+    proof: Vec<BytesN<32>>,
+) -> Result<u64, ContractError> {
+    if env
+        .storage()
+        .instance()
+        .get::<_, bool>(&DataKey::IsPaused)
+        .unwrap_or(false)
+    {
+        return Err(ContractError::ContractPaused);
+    }
+
+    let mut campaign = get_campaign(env, campaign_id)?;
+    if env.ledger().timestamp() > campaign.expires_at {
+        return Err(ContractError::ClaimExpired);
+    }
+
+    let recipient = leaf.recipient.clone();
+    let token_id = redeem_leaf(
+        env,
+        campaign_id,
+        &mut campaign,
+        leaf,
+        attributes,
+        &proof,
+        &recipient,
+    )?;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Airdrop(campaign_id), &campaign);
+    Ok(token_id)
+}
+
+/// After expiry, mints unclaimed allocations to `to` (e.g. a treasury) so the
+/// artwork is not lost. Bounded by `MAX_BATCH_SIZE` per call.
+pub fn sweep_airdrop(
+    env: &Env,
+    caller: &Address,
+    campaign_id: u32,
+    to: &Address,
+    leaves: Vec<AirdropLeaf>,
+    attributes: Vec<Vec<TokenAttribute>>,
+    proofs: Vec<Vec<BytesN<32>>>,
+) -> Result<Vec<u64>, ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+
+    let n = leaves.len();
+    if n != attributes.len() || n != proofs.len() {
+        return Err(ContractError::MismatchedArrays);
+    }
+    if n == 0 || n > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge);
+    }
+
+    let mut campaign = get_campaign(env, campaign_id)?;
+    if env.ledger().timestamp() <= campaign.expires_at {
+        return Err(ContractError::ClaimNotExpired);
+    }
+
+    let mut ids: Vec<u64> = Vec::new(env);
+    for i in 0..n {
+        let id = redeem_leaf(
+            env,
+            campaign_id,
+            &mut campaign,
+            leaves.get(i).unwrap(),
+            attributes.get(i).unwrap(),
+            &proofs.get(i).unwrap(),
+            to,
+        )?;
+        ids.push_back(id);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Airdrop(campaign_id), &campaign);
+
+    events::emit_airdrop_swept(env, campaign_id, to.clone(), n);
+    Ok(ids)
+}
+
+pub fn get_airdrop(env: &Env, campaign_id: u32) -> Option<AirdropCampaign> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Airdrop(campaign_id))
+}
@@ -0,0 +1,176 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{CheckpointSeries, DataKey};
+use crate::token;
+use crate::types::Checkpoint;
+use soroban_sdk::{Address, Env};
+
+fn count(env: &Env, series: &CheckpointSeries) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CheckpointCount(series.clone()))
+        .unwrap_or(0)
+}
+
+fn get(env: &Env, series: &CheckpointSeries, index: u32) -> Checkpoint {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Checkpoint(series.clone(), index))
+        .unwrap()
+}
+
+/// Records `value` for the current ledger. Several writes within one ledger
+/// collapse into a single checkpoint holding the last value.
+pub fn push(env: &Env, series: &CheckpointSeries, value: u64) {
+    let ledger = env.ledger().sequence();
+    let n = count(env, series);
+    if n > 0 && get(env, series, n - 1).ledger == ledger {
+        env.storage().persistent().set(
+            &DataKey::Checkpoint(series.clone(), n - 1),
+            &Checkpoint { ledger, value },
+        );
+        return;
+    }
+    env.storage().persistent().set(
+        &DataKey::Checkpoint(series.clone(), n),
+        &Checkpoint { ledger, value },
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::CheckpointCount(series.clone()), &(n + 1));
+}
+
+pub fn latest(env: &Env, series: &CheckpointSeries) -> u64 {
+    let n = count(env, series);
+    if n == 0 {
+        return 0;
+    }
+    get(env, series, n - 1).value
+}
+
+/// Binary search for the last checkpoint at or before `ledger_seq`. Only
+/// closed ledgers can be queried; the current one may still change.
+pub fn value_at(
+    env: &Env,
+    series: &CheckpointSeries,
+    ledger_seq: u32,
+) -> Result<u64, ContractError> {
+    if ledger_seq >= env.ledger().sequence() {
+        return Err(ContractError::FutureLedger);
+    }
+
+    let mut low = 0u32;
+    let mut high = count(env, series);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if get(env, series, mid).ledger > ledger_seq {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    if low == 0 {
+        Ok(0)
+    } else {
+        Ok(get(env, series, low - 1).value)
+    }
+}
+
+/// Account currently receiving `owner`'s votes. Holders vote for themselves
+/// until they delegate.
+pub fn delegate_of(env: &Env, owner: &Address) -> Address {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Delegate(owner.clone()))
+        .unwrap_or(owner.clone())
+}
+
+fn move_votes(env: &Env, from: Option<&Address>, to: Option<&Address>, amount: u64) {
+    if amount == 0 || from == to {
+        return;
+    }
+    if let Some(from) = from {
+        let series = CheckpointSeries::Votes(from.clone());
+        let votes = latest(env, &series);
+        push(env, &series, votes.saturating_sub(amount));
+    }
+    if let Some(to) = to {
+        let series = CheckpointSeries::Votes(to.clone());
+        let votes = latest(env, &series);
+        push(env, &series, votes + amount);
+    }
+}
+
+pub fn on_supply_change(env: &Env, new_supply: u64) {
+    push(env, &CheckpointSeries::TotalSupply, new_supply);
+}
+
+/// Brings `owner`'s balance checkpoint up to the stored balance and moves
+/// the difference into their delegatee's votes. The latest `Balance`
+/// checkpoint is exactly what `owner` contributes to those votes, so tokens
+/// recorded before checkpoints existed are credited on first touch.
+pub fn sync_balance(env: &Env, owner: &Address) {
+    let series = CheckpointSeries::Balance(owner.clone());
+    let balance = token::balance_of(env, owner);
+    let counted = latest(env, &series);
+    if balance == counted {
+        return;
+    }
+    push(env, &series, balance);
+    let delegatee = delegate_of(env, owner);
+    if balance > counted {
+        move_votes(env, None, Some(&delegatee), balance - counted);
+    } else {
+        move_votes(env, Some(&delegatee), None, counted - balance);
+    }
+}
+
+pub fn sync_supply(env: &Env) {
+    let supply = token::total_supply(env);
+    if latest(env, &CheckpointSeries::TotalSupply) != supply {
+        push(env, &CheckpointSeries::TotalSupply, supply);
+    }
+}
+
+pub fn delegate(env: &Env, owner: &Address, to: &Address) {
+    let previous = delegate_of(env, owner);
+    if &previous == to {
+        return;
+    }
+    sync_balance(env, owner);
+
+    if to == owner {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegate(owner.clone()));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Delegate(owner.clone()), to);
+    }
+
+    move_votes(
+        env,
+        Some(&previous),
+        Some(to),
+        token::balance_of(env, owner),
+    );
+    events::emit_delegate_changed(env, owner.clone(), previous, to.clone());
+}
+
+pub fn balance_of_at(env: &Env, owner: &Address, ledger_seq: u32) -> Result<u64, ContractError> {
+    value_at(env, &CheckpointSeries::Balance(owner.clone()), ledger_seq)
+}
+
+pub fn total_supply_at(env: &Env, ledger_seq: u32) -> Result<u64, ContractError> {
+    value_at(env, &CheckpointSeries::TotalSupply, ledger_seq)
+}
+
+pub fn votes_of(env: &Env, account: &Address) -> u64 {
+    latest(env, &CheckpointSeries::Votes(account.clone()))
+}
+
+pub fn votes_of_at(env: &Env, account: &Address, ledger_seq: u32) -> Result<u64, ContractError> {
+    value_at(env, &CheckpointSeries::Votes(account.clone()), ledger_seq)
+}
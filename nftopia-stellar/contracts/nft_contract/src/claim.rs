@@ -0,0 +1,103 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::DataKey;
+use crate::token;
+use crate::transfer;
+use crate::types::PendingClaim;
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+
+/// Moves `token_id` into contract escrow until someone presents the preimage
+/// of `claim_hash` or the claim expires.
+///
+/// The escrowed token counts toward the contract's own balance, so supply,
+/// balances and `Transfer` events stay consistent while the claim is open.
+/// The contract is never listed as a holder and gets no votes for it.
+pub fn transfer_to_claim(
+    env: &Env,
+    caller: &Address,
+    token_id: u64,
+    claim_hash: BytesN<32>,
+    expires_at: u64,
+) -> Result<(), ContractError> {
+    if !transfer::is_approved_or_owner(env, caller, token_id) {
+        return Err(ContractError::NotApproved);
+    }
+    if expires_at <= env.ledger().timestamp() {
+        return Err(ContractError::ClaimExpired);
+    }
+
+    let sender = token::owner_of(env, token_id)?;
+    let escrow = env.current_contract_address();
+    transfer::do_transfer(env, caller, &sender, &escrow, token_id)?;
+
+    env.storage().persistent().set(
+        &DataKey::PendingClaim(token_id),
+        &PendingClaim {
+            sender: sender.clone(),
+            claim_hash,
+            expires_at,
+        },
+    );
+
+    events::emit_claim_created(env, sender, token_id, expires_at);
+    Ok(())
+}
+
+/// Releases an escrowed token to `recipient` for whoever knows the preimage.
+///
+/// The preimage is visible once submitted, so senders should share it only
+/// with the intended claimant and keep expiries short.
+pub fn claim(
+    env: &Env,
+    token_id: u64,
+    preimage: Bytes,
+    recipient: &Address,
+) -> Result<(), ContractError> {
+    let pending = pending_claim(env, token_id).ok_or(ContractError::NotFound)?;
+    if env.ledger().timestamp() > pending.expires_at {
+        return Err(ContractError::ClaimExpired);
+    }
+    if env.crypto().sha256(&preimage).to_bytes() != pending.claim_hash {
+        return Err(ContractError::NotAuthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingClaim(token_id));
+    let escrow = env.current_contract_address();
+    transfer::do_transfer(env, &escrow, &escrow, recipient, token_id)?;
+
+    events::emit_claimed(env, recipient.clone(), token_id);
+    Ok(())
+}
+
+/// Returns an expired, unclaimed token to the address that parked it.
+pub fn reclaim(env: &Env, caller: &Address, token_id: u64) -> Result<(), ContractError> {
+    let pending = pending_claim(env, token_id).ok_or(ContractError::NotFound)?;
+    if caller != &pending.sender {
+        return Err(ContractError::NotOwner);
+    }
+    if env.ledger().timestamp() <= pending.expires_at {
+        return Err(ContractError::ClaimNotExpired);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingClaim(token_id));
+    transfer::do_transfer(
+        env,
+        caller,
+        &env.current_contract_address(),
+        &pending.sender,
+        token_id,
+    )?;
+
+    events::emit_claim_reclaimed(env, pending.sender, token_id);
+    Ok(())
+}
+
+pub fn pending_claim(env: &Env, token_id: u64) -> Option<PendingClaim> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingClaim(token_id))
+}
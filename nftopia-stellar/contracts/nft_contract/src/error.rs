@@ -28,4 +28,20 @@ pub enum ContractError {
     MismatchedArrays = 22,
     AlreadyBurned = 23,
     BurnNotAllowed = 24,
+    FutureLedger = 25,
+    ClaimExpired = 26,
+    ClaimNotExpired = 27,
+    AlreadyClaimed = 28,
+    InvalidProof = 29,
+    TokenLocked = 30,
+    WalletLimitExceeded = 31,
+    TransferCooldown = 32,
+    TokenIdUnavailable = 33,
+    RecoveryNotReady = 34,
+    AlreadyApproved = 35,
+    MultisigRequired = 36,
+    ProposalExpired = 37,
+    ThresholdNotMet = 38,
+    RecipeMismatch = 39,
+    RecipeInactive = 40,
 }
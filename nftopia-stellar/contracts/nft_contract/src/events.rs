@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, Env, contractevent};
+use crate::types::TokenMedia;
+use soroban_sdk::{Address, BytesN, Env, String, Vec, contractevent};
 
 #[contractevent]
 #[derive(Clone, Debug)]
@@ -46,12 +47,238 @@ pub struct ApprovalForAll {
     pub approved: bool,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BatchTransfer {
+    pub operator: Address,
+    pub from: Address,
+    pub to: Vec<Address>, // recipient of each entry in `token_ids`
+    pub token_ids: Vec<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BatchApproval {
+    pub owner: Address,
+    pub approved: Address,
+    pub token_ids: Vec<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BatchMetadataUpdate {
+    pub token_ids: Vec<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RangeReserved {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RangeReleased {
+    pub start: u64,
+    pub end: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TransferRulesUpdate {
+    pub max_per_wallet: Option<u64>,
+    pub cooldown: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RuleExemptionUpdate {
+    pub address: Address,
+    pub exempt: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct Lock {
+    pub locker: Address,
+    pub token_id: u64,
+    pub unlock_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct Unlock {
+    pub locker: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ClaimCreated {
+    pub sender: Address,
+    pub token_id: u64,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct Claimed {
+    pub recipient: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ClaimReclaimed {
+    pub sender: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecipeCreated {
+    pub recipe_id: u32,
+    pub creator: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct Redeemed {
+    pub recipe_id: u32,
+    pub redeemer: Address,
+    pub token_ids: Vec<u64>,
+    pub output_token_id: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AdminActionProposed {
+    pub proposal_id: u32,
+    pub proposer: Address,
+    pub action: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AdminActionApproved {
+    pub proposal_id: u32,
+    pub approver: Address,
+    pub approvals: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AdminActionExecuted {
+    pub proposal_id: u32,
+    pub action: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct GuardiansUpdated {
+    pub owner: Address,
+    pub threshold: u32,
+    pub count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecoveryInitiated {
+    pub owner: Address,
+    pub new_owner: Address,
+    pub guardian: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecoveryApproved {
+    pub owner: Address,
+    pub guardian: Address,
+    pub approvals: u32,
+    pub ready_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecoveryCancelled {
+    pub owner: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RecoveryExecuted {
+    pub owner: Address,
+    pub new_owner: Address,
+    pub count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AirdropCreated {
+    pub campaign_id: u32,
+    pub root: BytesN<32>,
+    pub leaf_count: u32,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AirdropClaimed {
+    pub campaign_id: u32,
+    pub index: u32,
+    pub recipient: Address,
+    pub token_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AirdropSwept {
+    pub campaign_id: u32,
+    pub to: Address,
+    pub count: u32,
+}
+
 #[contractevent]
 #[derive(Clone, Debug)]
 pub struct MetadataUpdate {
     pub token_id: u64,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct TokenMediaUpdate {
+    pub token_id: u64,
+    pub updated_by: Address,
+    pub media: TokenMedia,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ContractUriUpdate {
+    pub uri: Option<String>, // `None` when cleared
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DelegateChanged {
+    pub delegator: Address,
+    pub from_delegate: Address,
+    pub to_delegate: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StorageMigrated {
+    pub count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ContractUpgraded {
+    pub wasm_hash: BytesN<32>,
+}
+
 #[contractevent]
 #[derive(Clone, Debug)]
 pub struct RoyaltyUpdate {
@@ -89,6 +316,35 @@ pub fn emit_approval(env: &Env, owner: Address, approved: Address, token_id: u64
     .publish(env);
 }
 
+pub fn emit_batch_transfer(
+    env: &Env,
+    operator: Address,
+    from: Address,
+    to: Vec<Address>,
+    token_ids: Vec<u64>,
+) {
+    BatchTransfer {
+        operator,
+        from,
+        to,
+        token_ids,
+    }
+    .publish(env);
+}
+
+pub fn emit_batch_approval(env: &Env, owner: Address, approved: Address, token_ids: Vec<u64>) {
+    BatchApproval {
+        owner,
+        approved,
+        token_ids,
+    }
+    .publish(env);
+}
+
+pub fn emit_batch_metadata_update(env: &Env, token_ids: Vec<u64>) {
+    BatchMetadataUpdate { token_ids }.publish(env);
+}
+
 pub fn emit_approval_for_all(env: &Env, owner: Address, operator: Address, approved: bool) {
     ApprovalForAll {
         owner,
@@ -98,10 +354,233 @@ pub fn emit_approval_for_all(env: &Env, owner: Address, operator: Address, appro
     .publish(env);
 }
 
+pub fn emit_range_reserved(env: &Env, start: u64, end: u64) {
+    RangeReserved { start, end }.publish(env);
+}
+
+pub fn emit_range_released(env: &Env, start: u64, end: u64) {
+    RangeReleased { start, end }.publish(env);
+}
+
+pub fn emit_transfer_rules_update(env: &Env, max_per_wallet: Option<u64>, cooldown: u64) {
+    TransferRulesUpdate {
+        max_per_wallet,
+        cooldown,
+    }
+    .publish(env);
+}
+
+pub fn emit_rule_exemption_update(env: &Env, address: Address, exempt: bool) {
+    RuleExemptionUpdate { address, exempt }.publish(env);
+}
+
+pub fn emit_lock(env: &Env, locker: Address, token_id: u64, unlock_at: u64) {
+    Lock {
+        locker,
+        token_id,
+        unlock_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_unlock(env: &Env, locker: Address, token_id: u64) {
+    Unlock { locker, token_id }.publish(env);
+}
+
+pub fn emit_claim_created(env: &Env, sender: Address, token_id: u64, expires_at: u64) {
+    ClaimCreated {
+        sender,
+        token_id,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_claimed(env: &Env, recipient: Address, token_id: u64) {
+    Claimed {
+        recipient,
+        token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_claim_reclaimed(env: &Env, sender: Address, token_id: u64) {
+    ClaimReclaimed { sender, token_id }.publish(env);
+}
+
+pub fn emit_recipe_created(env: &Env, recipe_id: u32, creator: Address) {
+    RecipeCreated { recipe_id, creator }.publish(env);
+}
+
+pub fn emit_redeemed(
+    env: &Env,
+    recipe_id: u32,
+    redeemer: Address,
+    token_ids: Vec<u64>,
+    output_token_id: Option<u64>,
+) {
+    Redeemed {
+        recipe_id,
+        redeemer,
+        token_ids,
+        output_token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_admin_action_proposed(env: &Env, proposal_id: u32, proposer: Address, action: u32) {
+    AdminActionProposed {
+        proposal_id,
+        proposer,
+        action,
+    }
+    .publish(env);
+}
+
+pub fn emit_admin_action_approved(env: &Env, proposal_id: u32, approver: Address, approvals: u32) {
+    AdminActionApproved {
+        proposal_id,
+        approver,
+        approvals,
+    }
+    .publish(env);
+}
+
+pub fn emit_admin_action_executed(env: &Env, proposal_id: u32, action: u32) {
+    AdminActionExecuted {
+        proposal_id,
+        action,
+    }
+    .publish(env);
+}
+
+pub fn emit_guardians_updated(env: &Env, owner: Address, threshold: u32, count: u32) {
+    GuardiansUpdated {
+        owner,
+        threshold,
+        count,
+    }
+    .publish(env);
+}
+
+pub fn emit_recovery_initiated(env: &Env, owner: Address, new_owner: Address, guardian: Address) {
+    RecoveryInitiated {
+        owner,
+        new_owner,
+        guardian,
+    }
+    .publish(env);
+}
+
+pub fn emit_recovery_approved(
+    env: &Env,
+    owner: Address,
+    guardian: Address,
+    approvals: u32,
+    ready_at: u64,
+) {
+    RecoveryApproved {
+        owner,
+        guardian,
+        approvals,
+        ready_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_recovery_cancelled(env: &Env, owner: Address) {
+    RecoveryCancelled { owner }.publish(env);
+}
+
+pub fn emit_recovery_executed(env: &Env, owner: Address, new_owner: Address, count: u32) {
+    RecoveryExecuted {
+        owner,
+        new_owner,
+        count,
+    }
+    .publish(env);
+}
+
+pub fn emit_airdrop_created(
+    env: &Env,
+    campaign_id: u32,
+    root: BytesN<32>,
+    leaf_count: u32,
+    expires_at: u64,
+) {
+    AirdropCreated {
+        campaign_id,
+        root,
+        leaf_count,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_airdrop_claimed(
+    env: &Env,
+    campaign_id: u32,
+    index: u32,
+    recipient: Address,
+    token_id: u64,
+) {
+    AirdropClaimed {
+        campaign_id,
+        index,
+        recipient,
+        token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_airdrop_swept(env: &Env, campaign_id: u32, to: Address, count: u32) {
+    AirdropSwept {
+        campaign_id,
+        to,
+        count,
+    }
+    .publish(env);
+}
+
 pub fn emit_metadata_update(env: &Env, token_id: u64) {
     MetadataUpdate { token_id }.publish(env);
 }
 
+pub fn emit_token_media_update(env: &Env, token_id: u64, updated_by: Address, media: TokenMedia) {
+    TokenMediaUpdate {
+        token_id,
+        updated_by,
+        media,
+    }
+    .publish(env);
+}
+
+pub fn emit_contract_uri_update(env: &Env, uri: Option<String>) {
+    ContractUriUpdate { uri }.publish(env);
+}
+
+pub fn emit_delegate_changed(
+    env: &Env,
+    delegator: Address,
+    from_delegate: Address,
+    to_delegate: Address,
+) {
+    DelegateChanged {
+        delegator,
+        from_delegate,
+        to_delegate,
+    }
+    .publish(env);
+}
+
+pub fn emit_storage_migrated(env: &Env, count: u32) {
+    StorageMigrated { count }.publish(env);
+}
+
+pub fn emit_contract_upgraded(env: &Env, wasm_hash: BytesN<32>) {
+    ContractUpgraded { wasm_hash }.publish(env);
+}
+
 pub fn emit_royalty_update(env: &Env, recipient: Address, percentage: u32) {
     RoyaltyUpdate {
         recipient,
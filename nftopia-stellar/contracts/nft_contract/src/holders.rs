@@ -0,0 +1,102 @@
+use crate::error::ContractError;
+use crate::storage::{DataKey, MAX_HOLDER_PAGE, MAX_PAGE_SIZE};
+use crate::token;
+use soroban_sdk::{Address, Env, Vec};
+
+/// Appends `owner` to the holder list; called on a 0 → 1 balance change.
+pub fn add_holder(env: &Env, owner: &Address) {
+    let count = token::holder_count(env);
+    let index = count as u32;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Holder(index), owner);
+    env.storage()
+        .persistent()
+        .set(&DataKey::HolderIndex(owner.clone()), &index);
+    env.storage()
+        .instance()
+        .set(&DataKey::HolderCount, &(count + 1));
+}
+
+/// Swap-removes `owner` from the holder list; called on a 1 → 0 balance change.
+pub fn remove_holder(env: &Env, owner: &Address) {
+    let count = token::holder_count(env);
+    let Some(index) = env
+        .storage()
+        .persistent()
+        .get::<_, u32>(&DataKey::HolderIndex(owner.clone()))
+    else {
+        return;
+    };
+    let last = (count as u32).saturating_sub(1);
+    if index != last
+        && let Some(moved) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::Holder(last))
+    {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Holder(index), &moved);
+        env.storage()
+            .persistent()
+            .set(&DataKey::HolderIndex(moved), &index);
+    }
+    env.storage().persistent().remove(&DataKey::Holder(last));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HolderIndex(owner.clone()));
+    env.storage()
+        .instance()
+        .set(&DataKey::HolderCount, &count.saturating_sub(1));
+}
+
+/// Lists `owner` if they hold tokens but are missing from the holder list,
+/// as is the case for balances recorded before the list existed.
+pub fn sync_holder(env: &Env, owner: &Address) {
+    if token::balance_of(env, owner) > 0
+        && !env
+            .storage()
+            .persistent()
+            .has(&DataKey::HolderIndex(owner.clone()))
+    {
+        add_holder(env, owner);
+    }
+}
+
+/// Current holders with their balances, at most `MAX_HOLDER_PAGE` per call.
+/// Order is stable between calls but shifts when a holder exits, so take a
+/// snapshot within a single ledger.
+pub fn holders(env: &Env, offset: u32, limit: u32) -> Vec<(Address, u64)> {
+    let count = token::holder_count(env).min(u32::MAX as u64) as u32;
+    let end = offset.saturating_add(limit.min(MAX_HOLDER_PAGE)).min(count);
+    let mut page = Vec::new(env);
+    for i in offset..end {
+        if let Some(holder) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::Holder(i))
+        {
+            let balance = token::balance_of(env, &holder);
+            page.push_back((holder, balance));
+        }
+    }
+    page
+}
+
+/// Owners of `start_id..=end_id`, `None` for unminted or burned IDs.
+/// The range may span at most `MAX_PAGE_SIZE` IDs.
+pub fn owners_of_range(
+    env: &Env,
+    start_id: u64,
+    end_id: u64,
+) -> Result<Vec<Option<Address>>, ContractError> {
+    if end_id < start_id || end_id - start_id >= MAX_PAGE_SIZE as u64 {
+        return Err(ContractError::InvalidBatchSize);
+    }
+    let mut owners = Vec::new(env);
+    for token_id in start_id..=end_id {
+        owners.push_back(token::load_ownership(env, token_id).map(|o| o.0));
+    }
+    Ok(owners)
+}
@@ -1,22 +1,41 @@
 #![no_std]
 
 pub mod access_control;
+pub mod airdrop;
+pub mod checkpoints;
+pub mod claim;
 pub mod error;
 pub mod events;
+pub mod holders;
 pub mod interface;
+pub mod lock;
 pub mod metadata;
+pub mod migration;
+pub mod multisig;
+pub mod provenance;
+pub mod recovery;
+pub mod redemption;
+pub mod reservation;
 pub mod royalty;
 pub mod storage;
 pub mod token;
 pub mod transfer;
+pub mod transfer_rules;
 pub mod types;
 pub mod version;
 
 use crate::access_control as ac;
 use crate::error::ContractError;
 use crate::storage::DataKey;
-use crate::types::{CollectionConfig, RoyaltyInfo, TokenAttribute, TokenData};
-use soroban_sdk::{Address, Env, String, Vec, contract, contractimpl, panic_with_error};
+use crate::types::{
+    AdminCall, AdminProposal, AirdropCampaign, AirdropLeaf, CollectionConfig, CollectionInfo,
+    MultisigConfig, PendingClaim, ProvenanceEntry, Recipe, RecipeInput, RecipeOutput,
+    RecoveryConfig, RecoveryRequest, ReservedRange, RoyaltyInfo, TokenAttribute, TokenData,
+    TokenLock, TokenMedia, TransferRules, admin_action,
+};
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env, String, Vec, contract, contractimpl, panic_with_error,
+};
 
 #[contract]
 pub struct NftContract;
@@ -32,6 +51,7 @@ impl NftContract {
         admin: Address,
         config: CollectionConfig,
         default_royalty: Option<RoyaltyInfo>,
+        multisig: Option<MultisigConfig>,
     ) -> Result<(), ContractError> {
         if env.storage().instance().has(&DataKey::Admin) {
             panic_with_error!(&env, ContractError::AlreadyInitialized);
@@ -50,6 +70,9 @@ impl NftContract {
             .set(&DataKey::MetadataFrozen, &false);
 
         ac::init_owner(&env, &admin);
+        if let Some(config) = multisig {
+            multisig::init(&env, &admin, config)?;
+        }
 
         if let Some(royalty) = default_royalty {
             if royalty.percentage > crate::storage::MAX_ROYALTY_BPS {
@@ -86,6 +109,64 @@ impl NftContract {
         )
     }
 
+    /// Mints a token with a metadata content hash and optional image/animation fields.
+    pub fn mint_with_media(
+        env: Env,
+        caller: Address,
+        to: Address,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+        royalty_override: Option<RoyaltyInfo>,
+        media: TokenMedia,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+        token::mint_with_media(
+            &env,
+            &caller,
+            to,
+            metadata_uri,
+            attributes,
+            royalty_override,
+            media,
+        )
+    }
+
+    /// Mints a specific token ID, e.g. "#1 of 1"; fails with `TokenAlreadyExists` on collision.
+    /// An unreserved ID at or past the sequential cursor moves the cursor beyond it.
+    pub fn mint_with_id(
+        env: Env,
+        caller: Address,
+        to: Address,
+        token_id: u64,
+        metadata_uri: String,
+        attributes: Vec<TokenAttribute>,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+        token::mint_with_id(&env, &caller, to, token_id, metadata_uri, attributes)
+    }
+
+    /// Reserves IDs `start..=end` for `minters`; sequential minting skips them.
+    pub fn reserve_range(
+        env: Env,
+        caller: Address,
+        start: u64,
+        end: u64,
+        minters: Vec<Address>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        reservation::reserve_range(&env, &caller, start, end, minters)
+    }
+
+    pub fn release_range(env: Env, caller: Address, start: u64) -> Result<(), ContractError> {
+        caller.require_auth();
+        reservation::release_range(&env, &caller, start)
+    }
+
+    /// Reserved ranges; released ranges still ahead of the cursor have no minters.
+    pub fn reserved_ranges(env: Env) -> Vec<ReservedRange> {
+        reservation::get_ranges(&env)
+    }
+
     pub fn batch_mint(
         env: Env,
         caller: Address,
@@ -140,6 +221,142 @@ impl NftContract {
         token::batch_transfer(&env, &caller, from, to, token_ids)
     }
 
+    /// Sends each `(to, token_id)` pair from `from` in one atomic call.
+    pub fn batch_transfer_many(
+        env: Env,
+        caller: Address,
+        from: Address,
+        transfers: Vec<(Address, u64)>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        token::batch_transfer_many(&env, &caller, from, transfers)
+    }
+
+    // -------------------------------------------------------------------------
+    // Merkle airdrops
+    // -------------------------------------------------------------------------
+
+    /// Publishes a Merkle root of `AirdropLeaf` allocations claimable until `expires_at`.
+    pub fn create_airdrop(
+        env: Env,
+        caller: Address,
+        root: BytesN<32>,
+        leaf_count: u32,
+        expires_at: u64,
+    ) -> Result<u32, ContractError> {
+        caller.require_auth();
+        airdrop::create_airdrop(&env, &caller, root, leaf_count, expires_at)
+    }
+
+    /// Mints the recipient's allocation; the recipient pays for their own mint.
+    pub fn claim_airdrop(
+        env: Env,
+        campaign_id: u32,
+        leaf: AirdropLeaf,
+        attributes: Vec<TokenAttribute>,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<u64, ContractError> {
+        leaf.recipient.require_auth();
+        airdrop::claim_airdrop(&env, campaign_id, leaf, attributes, proof)
+    }
+
+    pub fn sweep_airdrop(
+        env: Env,
+        caller: Address,
+        campaign_id: u32,
+        to: Address,
+        leaves: Vec<AirdropLeaf>,
+        attributes: Vec<Vec<TokenAttribute>>,
+        proofs: Vec<Vec<BytesN<32>>>,
+    ) -> Result<Vec<u64>, ContractError> {
+        caller.require_auth();
+        airdrop::sweep_airdrop(&env, &caller, campaign_id, &to, leaves, attributes, proofs)
+    }
+
+    pub fn get_airdrop(env: Env, campaign_id: u32) -> Option<AirdropCampaign> {
+        airdrop::get_airdrop(&env, campaign_id)
+    }
+
+    pub fn is_airdrop_claimed(env: Env, campaign_id: u32, index: u32) -> bool {
+        airdrop::is_claimed(&env, campaign_id, index)
+    }
+
+    // -------------------------------------------------------------------------
+    // Burn-to-redeem
+    // -------------------------------------------------------------------------
+
+    /// Defines a recipe: burning tokens that satisfy `inputs` delivers `output`.
+    pub fn create_recipe(
+        env: Env,
+        caller: Address,
+        inputs: Vec<RecipeInput>,
+        output: RecipeOutput,
+    ) -> Result<u32, ContractError> {
+        caller.require_auth();
+        redemption::create_recipe(&env, &caller, inputs, output)
+    }
+
+    pub fn set_recipe_active(
+        env: Env,
+        caller: Address,
+        recipe_id: u32,
+        active: bool,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        redemption::set_recipe_active(&env, &caller, recipe_id, active)
+    }
+
+    pub fn recipe(env: Env, recipe_id: u32) -> Option<Recipe> {
+        redemption::get_recipe(&env, recipe_id)
+    }
+
+    /// Atomically burns `token_ids` (listed in recipe input order) and delivers
+    /// the recipe output. Returns the minted token ID, if any.
+    pub fn redeem(
+        env: Env,
+        redeemer: Address,
+        token_ids: Vec<u64>,
+        recipe_id: u32,
+    ) -> Result<Option<u64>, ContractError> {
+        redeemer.require_auth();
+        redemption::redeem(&env, &redeemer, token_ids, recipe_id)
+    }
+
+    // -------------------------------------------------------------------------
+    // Claimable transfers
+    // -------------------------------------------------------------------------
+
+    /// Parks `token_id` in escrow; whoever presents the SHA-256 preimage of
+    /// `claim_hash` before `expires_at` can claim it.
+    pub fn transfer_to_claim(
+        env: Env,
+        caller: Address,
+        token_id: u64,
+        claim_hash: BytesN<32>,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        claim::transfer_to_claim(&env, &caller, token_id, claim_hash, expires_at)
+    }
+
+    pub fn claim(
+        env: Env,
+        token_id: u64,
+        preimage: Bytes,
+        recipient: Address,
+    ) -> Result<(), ContractError> {
+        claim::claim(&env, token_id, preimage, &recipient)
+    }
+
+    pub fn reclaim(env: Env, caller: Address, token_id: u64) -> Result<(), ContractError> {
+        caller.require_auth();
+        claim::reclaim(&env, &caller, token_id)
+    }
+
+    pub fn pending_claim(env: Env, token_id: u64) -> Option<PendingClaim> {
+        claim::pending_claim(&env, token_id)
+    }
+
     // -------------------------------------------------------------------------
     // Ownership & Approvals
     // -------------------------------------------------------------------------
@@ -156,6 +373,20 @@ impl NftContract {
         token::total_supply(&env)
     }
 
+    /// Pages through current holders as `(address, balance)` pairs.
+    pub fn holders(env: Env, offset: u32, limit: u32) -> Vec<(Address, u64)> {
+        holders::holders(&env, offset, limit)
+    }
+
+    /// Owners of the token IDs `start_id..=end_id`; `None` where no token exists.
+    pub fn owners_of_range(
+        env: Env,
+        start_id: u64,
+        end_id: u64,
+    ) -> Result<Vec<Option<Address>>, ContractError> {
+        holders::owners_of_range(&env, start_id, end_id)
+    }
+
     pub fn approve(
         env: Env,
         owner: Address,
@@ -166,6 +397,16 @@ impl NftContract {
         transfer::approve(&env, &owner, &approved, token_id)
     }
 
+    pub fn batch_approve(
+        env: Env,
+        owner: Address,
+        approved: Address,
+        token_ids: Vec<u64>,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+        transfer::batch_approve(&env, &owner, &approved, token_ids)
+    }
+
     pub fn set_approval_for_all(env: Env, owner: Address, operator: Address, approved: bool) {
         owner.require_auth();
         transfer::set_approval_for_all(&env, &owner, &operator, approved);
@@ -179,6 +420,102 @@ impl NftContract {
         transfer::is_approved_for_all(&env, &owner, &operator)
     }
 
+    // -------------------------------------------------------------------------
+    // Token locks
+    // -------------------------------------------------------------------------
+
+    /// Freezes `token_id` in place for `locker`, which must hold the LOCKER role
+    /// and sign as well, since it takes control of settlement.
+    pub fn lock(
+        env: Env,
+        caller: Address,
+        token_id: u64,
+        locker: Address,
+        unlock_at: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        if locker != caller {
+            locker.require_auth();
+        }
+        lock::lock(&env, &caller, token_id, &locker, unlock_at)
+    }
+
+    pub fn unlock(env: Env, caller: Address, token_id: u64) -> Result<(), ContractError> {
+        caller.require_auth();
+        lock::unlock(&env, &caller, token_id)
+    }
+
+    pub fn get_lock(env: Env, token_id: u64) -> Option<TokenLock> {
+        lock::get_lock(&env, token_id)
+    }
+
+    pub fn is_locked(env: Env, token_id: u64) -> bool {
+        lock::is_locked(&env, token_id)
+    }
+
+    pub fn locked_tokens(env: Env, locker: Address, offset: u32, limit: u32) -> Vec<u64> {
+        lock::locked_tokens(&env, &locker, offset, limit)
+    }
+
+    // -------------------------------------------------------------------------
+    // Guardian recovery
+    // -------------------------------------------------------------------------
+
+    /// Opts `owner` into recovery: `threshold` of `guardians` can move their
+    /// tokens to a new address after `delay` seconds. Empty `guardians` opts out.
+    pub fn set_guardians(
+        env: Env,
+        owner: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+        delay: u64,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+        recovery::set_guardians(&env, &owner, guardians, threshold, delay)
+    }
+
+    pub fn guardians(env: Env, owner: Address) -> Option<RecoveryConfig> {
+        recovery::get_guardians(&env, &owner)
+    }
+
+    pub fn initiate_recovery(
+        env: Env,
+        guardian: Address,
+        owner: Address,
+        new_owner: Address,
+    ) -> Result<(), ContractError> {
+        guardian.require_auth();
+        recovery::initiate(&env, &guardian, &owner, new_owner)
+    }
+
+    pub fn approve_recovery(
+        env: Env,
+        guardian: Address,
+        owner: Address,
+    ) -> Result<(), ContractError> {
+        guardian.require_auth();
+        recovery::approve(&env, &guardian, &owner)
+    }
+
+    pub fn cancel_recovery(env: Env, owner: Address) -> Result<(), ContractError> {
+        owner.require_auth();
+        recovery::cancel(&env, &owner)
+    }
+
+    /// Moves a batch of `owner`'s tokens to the recovery address once the
+    /// challenge delay has passed. Returns the number moved.
+    pub fn execute_recovery(
+        env: Env,
+        owner: Address,
+        token_ids: Vec<u64>,
+    ) -> Result<u32, ContractError> {
+        recovery::execute(&env, &owner, token_ids)
+    }
+
+    pub fn recovery_request(env: Env, owner: Address) -> Option<RecoveryRequest> {
+        recovery::get_request(&env, &owner)
+    }
+
     // -------------------------------------------------------------------------
     // Metadata
     // -------------------------------------------------------------------------
@@ -201,6 +538,61 @@ impl NftContract {
         metadata::set_token_uri(&env, &caller, token_id, uri)
     }
 
+    pub fn batch_set_token_uri(
+        env: Env,
+        caller: Address,
+        token_ids: Vec<u64>,
+        uris: Vec<String>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        metadata::batch_set_token_uri(&env, &caller, token_ids, uris)
+    }
+
+    /// Chain of custody for `token_id`, oldest retained entry first.
+    pub fn provenance(env: Env, token_id: u64, offset: u32, limit: u32) -> Vec<ProvenanceEntry> {
+        provenance::provenance(&env, token_id, offset, limit)
+    }
+
+    /// Sets how many provenance entries each token keeps (0 disables logging).
+    pub fn set_provenance_cap(env: Env, caller: Address, cap: u32) -> Result<(), ContractError> {
+        caller.require_auth();
+        provenance::set_cap(&env, &caller, cap)
+    }
+
+    pub fn provenance_cap(env: Env) -> u32 {
+        provenance::get_cap(&env)
+    }
+
+    /// Deletes provenance entries left over from a higher cap; returns the count removed.
+    pub fn trim_provenance(env: Env, token_id: u64) -> u32 {
+        provenance::trim(&env, token_id)
+    }
+
+    pub fn token_media(env: Env, token_id: u64) -> Result<TokenMedia, ContractError> {
+        metadata::token_media(&env, token_id)
+    }
+
+    /// Replaces the token's media fields; blocked once metadata is frozen. The
+    /// owner may change the URIs, only the creator or a metadata updater the hashes.
+    pub fn set_token_media(
+        env: Env,
+        caller: Address,
+        token_id: u64,
+        media: TokenMedia,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        metadata::set_token_media(&env, &caller, token_id, media)
+    }
+
+    /// Checks `hash` against the token's recorded metadata content hash.
+    pub fn verify_metadata(
+        env: Env,
+        token_id: u64,
+        hash: BytesN<32>,
+    ) -> Result<bool, ContractError> {
+        metadata::verify_metadata(&env, token_id, hash)
+    }
+
     pub fn set_base_uri(env: Env, caller: Address, base_uri: String) -> Result<(), ContractError> {
         caller.require_auth();
         metadata::set_base_uri(&env, &caller, base_uri)
@@ -215,6 +607,25 @@ impl NftContract {
         metadata::is_metadata_frozen(&env)
     }
 
+    pub fn contract_uri(env: Env) -> Option<String> {
+        metadata::contract_uri(&env)
+    }
+
+    pub fn set_contract_uri(env: Env, caller: Address, uri: String) -> Result<(), ContractError> {
+        caller.require_auth();
+        metadata::set_contract_uri(&env, &caller, uri)
+    }
+
+    pub fn clear_contract_uri(env: Env, caller: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        metadata::clear_contract_uri(&env, &caller)
+    }
+
+    /// Returns config, supply counters, pause/freeze state and default royalty in one call.
+    pub fn collection_info(env: Env) -> Result<CollectionInfo, ContractError> {
+        metadata::collection_info(&env)
+    }
+
     // -------------------------------------------------------------------------
     // Royalty
     // -------------------------------------------------------------------------
@@ -248,6 +659,38 @@ impl NftContract {
         royalty::get_royalty_info(&env, token_id, sale_price)
     }
 
+    // -------------------------------------------------------------------------
+    // Governance snapshots
+    // -------------------------------------------------------------------------
+
+    /// Balance of `owner` at the end of ledger `ledger_seq`, which must have closed.
+    pub fn balance_of_at(env: Env, owner: Address, ledger_seq: u32) -> Result<u64, ContractError> {
+        checkpoints::balance_of_at(&env, &owner, ledger_seq)
+    }
+
+    pub fn total_supply_at(env: Env, ledger_seq: u32) -> Result<u64, ContractError> {
+        checkpoints::total_supply_at(&env, ledger_seq)
+    }
+
+    /// Delegates the voting weight of all of `owner`'s tokens to `to`.
+    /// Delegating to `owner` itself restores self-delegation.
+    pub fn delegate(env: Env, owner: Address, to: Address) {
+        owner.require_auth();
+        checkpoints::delegate(&env, &owner, &to);
+    }
+
+    pub fn get_delegate(env: Env, owner: Address) -> Address {
+        checkpoints::delegate_of(&env, &owner)
+    }
+
+    pub fn votes_of(env: Env, account: Address) -> u64 {
+        checkpoints::votes_of(&env, &account)
+    }
+
+    pub fn votes_of_at(env: Env, account: Address, ledger_seq: u32) -> Result<u64, ContractError> {
+        checkpoints::votes_of_at(&env, &account, ledger_seq)
+    }
+
     // -------------------------------------------------------------------------
     // Access control
     // -------------------------------------------------------------------------
@@ -266,6 +709,47 @@ impl NftContract {
         ac::has_role(&env, &address, role)
     }
 
+    // -------------------------------------------------------------------------
+    // Multi-signature admin actions
+    // -------------------------------------------------------------------------
+
+    /// Proposes a protected admin call; the proposer's approval counts toward the threshold.
+    pub fn propose_admin_action(
+        env: Env,
+        proposer: Address,
+        call: AdminCall,
+    ) -> Result<u32, ContractError> {
+        proposer.require_auth();
+        multisig::propose(&env, &proposer, call)
+    }
+
+    pub fn approve_admin_action(
+        env: Env,
+        approver: Address,
+        proposal_id: u32,
+    ) -> Result<(), ContractError> {
+        approver.require_auth();
+        multisig::approve(&env, &approver, proposal_id)
+    }
+
+    /// Executes a proposal once enough current OWNER/ADMIN holders have approved it.
+    pub fn execute_admin_action(
+        env: Env,
+        caller: Address,
+        proposal_id: u32,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        multisig::execute(&env, &caller, proposal_id)
+    }
+
+    pub fn admin_proposal(env: Env, proposal_id: u32) -> Option<AdminProposal> {
+        multisig::get_proposal(&env, proposal_id)
+    }
+
+    pub fn multisig_config(env: Env) -> Option<MultisigConfig> {
+        multisig::get_config(&env)
+    }
+
     // -------------------------------------------------------------------------
     // Admin controls
     // -------------------------------------------------------------------------
@@ -273,6 +757,7 @@ impl NftContract {
     pub fn set_pause(env: Env, caller: Address, paused: bool) {
         caller.require_auth();
         ac::require_admin_or_owner(&env, &caller);
+        multisig::require_unprotected(&env, admin_action::SET_PAUSE);
         env.storage().instance().set(&DataKey::IsPaused, &paused);
     }
 
@@ -283,6 +768,47 @@ impl NftContract {
             .unwrap_or(false)
     }
 
+    /// Configures the per-wallet holding cap and post-receipt transfer cooldown.
+    pub fn set_transfer_rules(
+        env: Env,
+        caller: Address,
+        max_per_wallet: Option<u64>,
+        cooldown: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+        transfer_rules::set_rules(&env, &caller, max_per_wallet, cooldown)
+    }
+
+    pub fn transfer_rules(env: Env) -> TransferRules {
+        transfer_rules::get_rules(&env)
+    }
+
+    pub fn set_rule_exemption(env: Env, caller: Address, address: Address, exempt: bool) {
+        caller.require_auth();
+        transfer_rules::set_exemption(&env, &caller, &address, exempt);
+    }
+
+    pub fn is_rule_exempt(env: Env, address: Address) -> bool {
+        transfer_rules::is_exempt(&env, &address)
+    }
+
+    /// Replaces the contract code; admin or owner only, by proposal under multisig.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        caller.require_auth();
+        migration::upgrade(&env, &caller, new_wasm_hash);
+    }
+
+    /// Converts legacy-layout tokens to the split ownership/metadata layout
+    /// and indexes their owners for holder and checkpoint queries.
+    pub fn migrate_token_storage(
+        env: Env,
+        caller: Address,
+        token_ids: Vec<u64>,
+    ) -> Result<u32, ContractError> {
+        caller.require_auth();
+        migration::migrate_token_storage(&env, &caller, token_ids)
+    }
+
     // -------------------------------------------------------------------------
     // Interface detection (ERC-165 equivalent)
     // -------------------------------------------------------------------------
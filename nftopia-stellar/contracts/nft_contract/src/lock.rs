@@ -0,0 +1,136 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{DataKey, MAX_PAGE_SIZE};
+use crate::token;
+use crate::transfer;
+use crate::types::{TokenLock, role};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Freezes `token_id` in place on behalf of `locker`.
+///
+/// While locked, transfers, burns and approvals are rejected. The locker may
+/// still move the token to settle (see `transfer::authorize_transfer`), and
+/// the owner may unlock once `unlock_at` has passed.
+pub fn lock(
+    env: &Env,
+    caller: &Address,
+    token_id: u64,
+    locker: &Address,
+    unlock_at: u64,
+) -> Result<(), ContractError> {
+    if !transfer::is_approved_or_owner(env, caller, token_id) {
+        return Err(ContractError::NotApproved);
+    }
+    if !access_control::has_role(env, locker, role::LOCKER) {
+        return Err(ContractError::NotAuthorized);
+    }
+    if is_locked(env, token_id) {
+        return Err(ContractError::TokenLocked);
+    }
+    let now = env.ledger().timestamp();
+    if unlock_at <= now {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let index = locked_count(env, locker);
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockerToken(locker.clone(), index), &token_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockerTokenCount(locker.clone()), &(index + 1));
+    env.storage().persistent().set(
+        &DataKey::TokenLock(token_id),
+        &TokenLock {
+            locker: locker.clone(),
+            locked_at: now,
+            unlock_at,
+            index,
+        },
+    );
+
+    events::emit_lock(env, locker.clone(), token_id, unlock_at);
+    Ok(())
+}
+
+/// The locker can release at any time; the owner only after `unlock_at`.
+pub fn unlock(env: &Env, caller: &Address, token_id: u64) -> Result<(), ContractError> {
+    let lock = get_lock(env, token_id).ok_or(ContractError::NotFound)?;
+    let is_locker = caller == &lock.locker;
+    let owner_may_unlock =
+        caller == &token::owner_of(env, token_id)? && env.ledger().timestamp() >= lock.unlock_at;
+    if !is_locker && !owner_may_unlock {
+        return Err(ContractError::NotAuthorized);
+    }
+    release(env, token_id, lock);
+    Ok(())
+}
+
+/// Removes the lock and swap-removes the token from the locker's index.
+pub fn release(env: &Env, token_id: u64, lock: TokenLock) {
+    let last = locked_count(env, &lock.locker) - 1;
+    if lock.index != last {
+        let moved: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LockerToken(lock.locker.clone(), last))
+            .unwrap();
+        env.storage().persistent().set(
+            &DataKey::LockerToken(lock.locker.clone(), lock.index),
+            &moved,
+        );
+        let mut moved_lock = get_lock(env, moved).unwrap();
+        moved_lock.index = lock.index;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenLock(moved), &moved_lock);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::LockerToken(lock.locker.clone(), last));
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockerTokenCount(lock.locker.clone()), &last);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TokenLock(token_id));
+
+    events::emit_unlock(env, lock.locker, token_id);
+}
+
+pub fn get_lock(env: &Env, token_id: u64) -> Option<TokenLock> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenLock(token_id))
+}
+
+pub fn is_locked(env: &Env, token_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::TokenLock(token_id))
+}
+
+pub fn locked_count(env: &Env, locker: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LockerTokenCount(locker.clone()))
+        .unwrap_or(0)
+}
+
+/// Tokens currently locked by `locker`, at most `MAX_PAGE_SIZE` per call.
+pub fn locked_tokens(env: &Env, locker: &Address, offset: u32, limit: u32) -> Vec<u64> {
+    let count = locked_count(env, locker);
+    let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+    let mut ids: Vec<u64> = Vec::new(env);
+    for i in offset..end {
+        if let Some(id) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LockerToken(locker.clone(), i))
+        {
+            ids.push_back(id);
+        }
+    }
+    ids
+}
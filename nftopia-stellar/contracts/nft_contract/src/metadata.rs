@@ -1,16 +1,16 @@
 use crate::access_control;
 use crate::error::ContractError;
 use crate::events;
-use crate::storage::DataKey;
-use crate::types::TokenData;
-use soroban_sdk::{Address, Env, String};
+use crate::multisig;
+use crate::storage::{DataKey, MAX_BATCH_SIZE};
+use crate::token;
+use crate::types::{
+    CollectionConfig, CollectionInfo, RoyaltyInfo, TokenData, TokenMedia, admin_action,
+};
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 pub fn token_uri(env: &Env, token_id: u64) -> Result<String, ContractError> {
-    let data: TokenData = env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenData(token_id))
-        .ok_or(ContractError::TokenNotFound)?;
+    let meta = token::load_metadata(env, token_id).ok_or(ContractError::TokenNotFound)?;
 
     let base: Option<String> = env.storage().instance().get(&DataKey::BaseUri);
     if let Some(base_uri) = base
@@ -18,14 +18,25 @@ pub fn token_uri(env: &Env, token_id: u64) -> Result<String, ContractError> {
     {
         return Ok(base_uri);
     }
-    Ok(data.metadata_uri)
+    Ok(meta.metadata_uri)
 }
 
+/// Joins the hot and cold records, so `owner` always reflects the current holder.
 pub fn token_metadata(env: &Env, token_id: u64) -> Result<TokenData, ContractError> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::TokenData(token_id))
-        .ok_or(ContractError::TokenNotFound)
+    let owner = token::owner_of(env, token_id)?;
+    let meta = token::load_metadata(env, token_id).ok_or(ContractError::TokenNotFound)?;
+    Ok(TokenData {
+        id: token_id,
+        owner,
+        metadata_uri: meta.metadata_uri,
+        created_at: meta.created_at,
+        creator: meta.creator,
+        royalty_percentage: meta.royalty_percentage,
+        royalty_recipient: meta.royalty_recipient,
+        attributes: meta.attributes,
+        edition_number: meta.edition_number,
+        total_editions: meta.total_editions,
+    })
 }
 
 pub fn set_token_uri(
@@ -43,51 +54,140 @@ pub fn set_token_uri(
         return Err(ContractError::MetadataFrozen);
     }
 
-    let owner: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenOwner(token_id))
-        .ok_or(ContractError::TokenNotFound)?;
+    let owner = token::owner_of(env, token_id)?;
 
     // Only the token owner may update the URI
     if caller != &owner {
         return Err(ContractError::NotAuthorized);
     }
 
-    let mut data: TokenData = env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenData(token_id))
-        .ok_or(ContractError::TokenNotFound)?;
-    data.metadata_uri = uri;
-    env.storage()
-        .persistent()
-        .set(&DataKey::TokenData(token_id), &data);
+    let mut meta = token::load_metadata(env, token_id).ok_or(ContractError::TokenNotFound)?;
+    meta.metadata_uri = uri;
+    token::save_metadata(env, token_id, &meta);
 
     events::emit_metadata_update(env, token_id);
     Ok(())
 }
 
-pub fn set_base_uri(env: &Env, caller: &Address, base_uri: String) -> Result<(), ContractError> {
-    if env
+/// Sets `uris[i]` on `token_ids[i]` under the same rules as `set_token_uri`; all-or-nothing.
+pub fn batch_set_token_uri(
+    env: &Env,
+    caller: &Address,
+    token_ids: Vec<u64>,
+    uris: Vec<String>,
+) -> Result<(), ContractError> {
+    let n = token_ids.len();
+    if n != uris.len() {
+        return Err(ContractError::MismatchedArrays);
+    }
+    if n == 0 || n > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge);
+    }
+    for (token_id, uri) in token_ids.iter().zip(uris.iter()) {
+        set_token_uri(env, caller, token_id, uri)?;
+    }
+    events::emit_batch_metadata_update(env, token_ids);
+    Ok(())
+}
+
+pub fn token_media(env: &Env, token_id: u64) -> Result<TokenMedia, ContractError> {
+    if !token::exists(env, token_id) {
+        return Err(ContractError::TokenNotFound);
+    }
+    Ok(env
         .storage()
-        .instance()
-        .get::<_, bool>(&DataKey::MetadataFrozen)
-        .unwrap_or(false)
+        .persistent()
+        .get(&DataKey::TokenMedia(token_id))
+        .unwrap_or_default())
+}
+
+/// Stores `media` for a token, or clears the entry when every field is empty.
+pub fn save_media(env: &Env, token_id: u64, media: &TokenMedia) -> Result<(), ContractError> {
+    for uri in [&media.image_uri, &media.animation_url]
+        .into_iter()
+        .flatten()
     {
+        if uri.is_empty() {
+            return Err(ContractError::InvalidUri);
+        }
+    }
+    let key = DataKey::TokenMedia(token_id);
+    if media == &TokenMedia::default() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, media);
+    }
+    Ok(())
+}
+
+/// Updates a token's media fields. The owner may change the URIs, but the
+/// content and media hashes back `verify_metadata`, so only the token's
+/// creator or a `METADATA_UPDATER` may change them.
+pub fn set_token_media(
+    env: &Env,
+    caller: &Address,
+    token_id: u64,
+    media: TokenMedia,
+) -> Result<(), ContractError> {
+    if is_metadata_frozen(env) {
         return Err(ContractError::MetadataFrozen);
     }
+
+    let current = token_media(env, token_id)?;
+    if current.image_uri != media.image_uri || current.animation_url != media.animation_url {
+        // Same rule as `set_token_uri`: only the token owner
+        if caller != &token::owner_of(env, token_id)? {
+            return Err(ContractError::NotAuthorized);
+        }
+    }
+    if current.content_hash != media.content_hash || current.media_hash != media.media_hash {
+        let creator = token::load_metadata(env, token_id)
+            .ok_or(ContractError::TokenNotFound)?
+            .creator;
+        if caller != &creator {
+            access_control::require_metadata_updater(env, caller)?;
+        }
+    }
+    if current == media {
+        return Ok(());
+    }
+
+    save_media(env, token_id, &media)?;
+    events::emit_token_media_update(env, token_id, caller.clone(), media);
+    events::emit_metadata_update(env, token_id);
+    Ok(())
+}
+
+/// True if `hash` matches the token's recorded `content_hash`; false when none was set.
+pub fn verify_metadata(env: &Env, token_id: u64, hash: BytesN<32>) -> Result<bool, ContractError> {
+    Ok(token_media(env, token_id)?.content_hash == Some(hash))
+}
+
+pub fn set_base_uri(env: &Env, caller: &Address, base_uri: String) -> Result<(), ContractError> {
     access_control::require_admin_or_owner(env, caller);
+    multisig::require_unprotected(env, admin_action::SET_BASE_URI);
+    apply_base_uri(env, base_uri)
+}
+
+pub fn apply_base_uri(env: &Env, base_uri: String) -> Result<(), ContractError> {
+    if is_metadata_frozen(env) {
+        return Err(ContractError::MetadataFrozen);
+    }
     env.storage().instance().set(&DataKey::BaseUri, &base_uri);
     Ok(())
 }
 
 pub fn freeze_metadata(env: &Env, caller: &Address) -> Result<(), ContractError> {
     access_control::require_owner(env, caller);
+    multisig::require_unprotected(env, admin_action::FREEZE_METADATA);
+    apply_freeze(env);
+    Ok(())
+}
+
+pub fn apply_freeze(env: &Env) {
     env.storage()
         .instance()
         .set(&DataKey::MetadataFrozen, &true);
-    Ok(())
 }
 
 pub fn is_metadata_frozen(env: &Env) -> bool {
@@ -96,3 +196,72 @@ pub fn is_metadata_frozen(env: &Env) -> bool {
         .get::<_, bool>(&DataKey::MetadataFrozen)
         .unwrap_or(false)
 }
+
+pub fn contract_uri(env: &Env) -> Option<String> {
+    env.storage().instance().get(&DataKey::ContractUri)
+}
+
+/// Sets the collection-level metadata URI (OpenSea `contractURI` equivalent).
+pub fn set_contract_uri(env: &Env, caller: &Address, uri: String) -> Result<(), ContractError> {
+    if is_metadata_frozen(env) {
+        return Err(ContractError::MetadataFrozen);
+    }
+    access_control::require_admin_or_metadata_updater(env, caller)?;
+    if uri.is_empty() {
+        return Err(ContractError::InvalidUri);
+    }
+    env.storage().instance().set(&DataKey::ContractUri, &uri);
+    events::emit_contract_uri_update(env, Some(uri));
+    Ok(())
+}
+
+/// Removes the contract URI, under the same rules as `set_contract_uri`.
+pub fn clear_contract_uri(env: &Env, caller: &Address) -> Result<(), ContractError> {
+    if is_metadata_frozen(env) {
+        return Err(ContractError::MetadataFrozen);
+    }
+    access_control::require_admin_or_metadata_updater(env, caller)?;
+    env.storage().instance().remove(&DataKey::ContractUri);
+    events::emit_contract_uri_update(env, None);
+    Ok(())
+}
+
+/// Aggregates collection-level state so clients can fetch it in a single call.
+pub fn collection_info(env: &Env) -> Result<CollectionInfo, ContractError> {
+    let mut config: CollectionConfig = env
+        .storage()
+        .instance()
+        .get(&DataKey::CollectionConfig)
+        .ok_or(ContractError::NotFound)?;
+
+    // `set_base_uri` and `freeze_metadata` write dedicated keys; reflect them here
+    if let Some(base_uri) = env.storage().instance().get::<_, String>(&DataKey::BaseUri) {
+        config.base_uri = base_uri;
+    }
+    let metadata_frozen = is_metadata_frozen(env);
+    config.metadata_is_frozen = metadata_frozen;
+
+    let default_royalty: Option<RoyaltyInfo> =
+        env.storage().instance().get(&DataKey::DefaultRoyalty);
+
+    Ok(CollectionInfo {
+        config,
+        contract_uri: contract_uri(env),
+        total_supply: token::total_supply(env),
+        burned_count: token::burned_count(env),
+        holder_count: token::holder_count(env),
+        next_token_id: env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .unwrap_or(1),
+        is_paused: env
+            .storage()
+            .instance()
+            .get::<_, bool>(&DataKey::IsPaused)
+            .unwrap_or(false),
+        metadata_frozen,
+        royalty_percentage: default_royalty.as_ref().map_or(0, |r| r.percentage),
+        royalty_recipient: default_royalty.map(|r| r.recipient),
+    })
+}
@@ -0,0 +1,101 @@
+use crate::access_control;
+use crate::checkpoints;
+use crate::error::ContractError;
+use crate::events;
+use crate::holders;
+use crate::multisig;
+use crate::storage::{DataKey, MAX_BATCH_SIZE};
+use crate::token;
+use crate::types::{TokenData, TokenMetadata, TokenOwnership, admin_action};
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+/// Replaces the contract code. Deployments from before the split layout
+/// upgrade through here and then call `migrate_token_storage`.
+pub fn upgrade(env: &Env, caller: &Address, new_wasm_hash: BytesN<32>) {
+    access_control::require_admin_or_owner(env, caller);
+    multisig::require_unprotected(env, admin_action::UPGRADE);
+    apply_upgrade(env, new_wasm_hash);
+}
+
+pub fn apply_upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    events::emit_contract_upgraded(env, new_wasm_hash);
+}
+
+/// Moves tokens minted under the legacy layout (`TokenData`, `TokenOwner`,
+/// `TokenApproved`) into the split `Token` / `TokenMeta` entries. The legacy
+/// `TokenOwner` entry is authoritative for ownership, since `TokenData.owner`
+/// was never updated on transfer.
+///
+/// Legacy deployments kept `Balance` and `TotalSupply` but no holder list or
+/// checkpoints, so each migrated owner is added to the holder list and their
+/// balance, votes and the total supply are checkpointed at the current ledger.
+///
+/// IDs that are already migrated or do not exist are skipped, so the call is
+/// safe to repeat. Returns the number of tokens migrated.
+pub fn migrate_token_storage(
+    env: &Env,
+    caller: &Address,
+    token_ids: Vec<u64>,
+) -> Result<u32, ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+
+    let n = token_ids.len();
+    if n == 0 || n > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge);
+    }
+
+    let mut migrated = 0u32;
+    for token_id in token_ids.iter() {
+        let legacy: Option<TokenData> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenData(token_id));
+        let Some(legacy) = legacy else {
+            continue;
+        };
+
+        let owner: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenOwner(token_id))
+            .unwrap_or(legacy.owner);
+        let approved: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenApproved(token_id));
+        token::save_ownership(env, token_id, &TokenOwnership(owner.clone(), approved));
+        token::save_metadata(
+            env,
+            token_id,
+            &TokenMetadata {
+                metadata_uri: legacy.metadata_uri,
+                created_at: legacy.created_at,
+                creator: legacy.creator,
+                royalty_percentage: legacy.royalty_percentage,
+                royalty_recipient: legacy.royalty_recipient,
+                attributes: legacy.attributes,
+                edition_number: legacy.edition_number,
+                total_editions: legacy.total_editions,
+            },
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TokenData(token_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TokenOwner(token_id));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TokenApproved(token_id));
+        holders::sync_holder(env, &owner);
+        checkpoints::sync_balance(env, &owner);
+        migrated += 1;
+    }
+    checkpoints::sync_supply(env);
+
+    events::emit_storage_migrated(env, migrated);
+    Ok(migrated)
+}
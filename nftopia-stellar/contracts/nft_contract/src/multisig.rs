@@ -0,0 +1,180 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::metadata;
+use crate::migration;
+use crate::royalty;
+use crate::storage::{DataKey, MultisigKey};
+use crate::types::{AdminCall, AdminProposal, MultisigConfig, admin_action, role};
+use soroban_sdk::{Address, Env, Vec, panic_with_error};
+
+pub fn get_config(env: &Env) -> Option<MultisigConfig> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Multisig(MultisigKey::Config))
+}
+
+/// A single key could otherwise grant ADMIN to keys it controls, or swap
+/// out the code enforcing the threshold.
+const ALWAYS_PROTECTED: [u32; 3] = [
+    admin_action::GRANT_ROLE,
+    admin_action::REVOKE_ROLE,
+    admin_action::UPGRADE,
+];
+
+pub fn get_proposal(env: &Env, proposal_id: u32) -> Option<AdminProposal> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Multisig(MultisigKey::Proposal(proposal_id)))
+}
+
+/// Validates and stores the config, granting ADMIN to each co-admin.
+/// Configs that leave role changes or upgrades unprotected are rejected.
+/// Called once from `initialize`.
+pub fn init(env: &Env, admin: &Address, config: MultisigConfig) -> Result<(), ContractError> {
+    let signers = config.co_admins.len() + 1;
+    if config.threshold == 0 || config.threshold > signers || config.proposal_ttl == 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    for action in config.protected_actions.iter() {
+        if action > admin_action::UPGRADE {
+            return Err(ContractError::InvalidAmount);
+        }
+    }
+    if ALWAYS_PROTECTED
+        .iter()
+        .any(|action| !config.protected_actions.contains(action))
+    {
+        return Err(ContractError::InvalidAmount);
+    }
+    for (i, co_admin) in config.co_admins.iter().enumerate() {
+        if &co_admin == admin || config.co_admins.first_index_of(&co_admin) != Some(i as u32) {
+            return Err(ContractError::InvalidRecipient);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Role(co_admin, role::ADMIN), &true);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::Multisig(MultisigKey::Config), &config);
+    Ok(())
+}
+
+pub fn is_protected(env: &Env, action: u32) -> bool {
+    get_config(env).is_some_and(|c| c.protected_actions.contains(action))
+}
+
+/// Rejects single-signer calls to an action that must go through a proposal.
+pub fn require_unprotected(env: &Env, action: u32) {
+    if is_protected(env, action) {
+        panic_with_error!(env, ContractError::MultisigRequired);
+    }
+}
+
+fn is_signer(env: &Env, address: &Address) -> bool {
+    access_control::has_role(env, address, role::OWNER)
+        || access_control::has_role(env, address, role::ADMIN)
+}
+
+/// Opens a proposal for `call`; the proposer's approval is counted.
+pub fn propose(env: &Env, proposer: &Address, call: AdminCall) -> Result<u32, ContractError> {
+    let config = get_config(env).ok_or(ContractError::NotFound)?;
+    if !is_signer(env, proposer) {
+        return Err(ContractError::NotAuthorized);
+    }
+
+    let count_key = DataKey::Multisig(MultisigKey::ProposalCount);
+    let proposal_id: u32 = env.storage().instance().get(&count_key).unwrap_or(0);
+    let action = call.action();
+    let proposal = AdminProposal {
+        call,
+        proposer: proposer.clone(),
+        approvals: Vec::from_array(env, [proposer.clone()]),
+        expires_at: env.ledger().timestamp().saturating_add(config.proposal_ttl),
+    };
+    env.storage().persistent().set(
+        &DataKey::Multisig(MultisigKey::Proposal(proposal_id)),
+        &proposal,
+    );
+    env.storage().instance().set(&count_key, &(proposal_id + 1));
+
+    events::emit_admin_action_proposed(env, proposal_id, proposer.clone(), action);
+    Ok(proposal_id)
+}
+
+pub fn approve(env: &Env, approver: &Address, proposal_id: u32) -> Result<(), ContractError> {
+    if !is_signer(env, approver) {
+        return Err(ContractError::NotAuthorized);
+    }
+    let mut proposal = open_proposal(env, proposal_id)?;
+    if proposal.approvals.contains(approver) {
+        return Err(ContractError::AlreadyApproved);
+    }
+
+    proposal.approvals.push_back(approver.clone());
+    env.storage().persistent().set(
+        &DataKey::Multisig(MultisigKey::Proposal(proposal_id)),
+        &proposal,
+    );
+    events::emit_admin_action_approved(
+        env,
+        proposal_id,
+        approver.clone(),
+        proposal.approvals.len(),
+    );
+    Ok(())
+}
+
+/// Runs an approved proposal. Approvals only count while the approver still
+/// holds OWNER or ADMIN, so revoking a signer also withdraws their votes.
+pub fn execute(env: &Env, caller: &Address, proposal_id: u32) -> Result<(), ContractError> {
+    let config = get_config(env).ok_or(ContractError::NotFound)?;
+    if !is_signer(env, caller) {
+        return Err(ContractError::NotAuthorized);
+    }
+    let proposal = open_proposal(env, proposal_id)?;
+    let approvals = proposal
+        .approvals
+        .iter()
+        .filter(|a| is_signer(env, a))
+        .count() as u32;
+    if approvals < config.threshold {
+        return Err(ContractError::ThresholdNotMet);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Multisig(MultisigKey::Proposal(proposal_id)));
+
+    let action = proposal.call.action();
+    match proposal.call {
+        AdminCall::SetPause(paused) => env.storage().instance().set(&DataKey::IsPaused, &paused),
+        AdminCall::SetDefaultRoyalty(recipient, percentage) => {
+            royalty::apply_default_royalty(env, recipient, percentage)?
+        }
+        AdminCall::SetTokenRoyalty(token_id, recipient, percentage) => {
+            royalty::apply_token_royalty(env, token_id, recipient, percentage)?
+        }
+        AdminCall::SetBaseUri(base_uri) => metadata::apply_base_uri(env, base_uri)?,
+        AdminCall::FreezeMetadata => metadata::apply_freeze(env),
+        AdminCall::GrantRole(target, role_disc) => {
+            access_control::apply_role(env, &target, role_disc, true)
+        }
+        AdminCall::RevokeRole(target, role_disc) => {
+            access_control::apply_role(env, &target, role_disc, false)
+        }
+        AdminCall::Upgrade(wasm_hash) => migration::apply_upgrade(env, wasm_hash),
+    }
+
+    events::emit_admin_action_executed(env, proposal_id, action);
+    Ok(())
+}
+
+fn open_proposal(env: &Env, proposal_id: u32) -> Result<AdminProposal, ContractError> {
+    let proposal = get_proposal(env, proposal_id).ok_or(ContractError::NotFound)?;
+    if env.ledger().timestamp() > proposal.expires_at {
+        return Err(ContractError::ProposalExpired);
+    }
+    Ok(proposal)
+}
@@ -0,0 +1,119 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::storage::{
+    DEFAULT_PROVENANCE_CAP, DataKey, MAX_PAGE_SIZE, MAX_PROVENANCE_CAP, MAX_PROVENANCE_TRIM,
+};
+use crate::types::{ProvenanceAction, ProvenanceEntry};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Maximum entries kept per token; 0 disables the log.
+pub fn get_cap(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ProvenanceCap)
+        .unwrap_or(DEFAULT_PROVENANCE_CAP)
+}
+
+/// Lowering the cap hides the excess entries at once. Their storage is
+/// reclaimed by each token's next log writes, or by `trim`.
+pub fn set_cap(env: &Env, caller: &Address, cap: u32) -> Result<(), ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+    if cap > MAX_PROVENANCE_CAP {
+        return Err(ContractError::InvalidAmount);
+    }
+    env.storage().instance().set(&DataKey::ProvenanceCap, &cap);
+    Ok(())
+}
+
+/// Total entries ever recorded for `token_id`, including rolled-off ones.
+fn recorded(env: &Env, token_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProvenanceCount(token_id))
+        .unwrap_or(0)
+}
+
+fn first_stored(env: &Env, token_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProvenanceFirst(token_id))
+        .unwrap_or(0)
+}
+
+/// Deletes up to `max` entries that fell outside `cap`, oldest first.
+/// Returns how many were deleted.
+fn trim_to(env: &Env, token_id: u64, cap: u32, max: u32) -> u32 {
+    let first = first_stored(env, token_id);
+    let end = recorded(env, token_id)
+        .saturating_sub(cap)
+        .min(first.saturating_add(max));
+    if end <= first {
+        return 0;
+    }
+    for seq in first..end {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Provenance(token_id, seq));
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProvenanceFirst(token_id), &end);
+    end - first
+}
+
+/// Deletes up to `MAX_PAGE_SIZE` entries of `token_id` left over from a
+/// higher cap. Open to anyone; returns how many were deleted.
+pub fn trim(env: &Env, token_id: u64) -> u32 {
+    trim_to(env, token_id, get_cap(env), MAX_PAGE_SIZE)
+}
+
+/// Appends an entry, dropping the oldest once the token holds `cap` entries.
+/// Entries above a since-lowered cap are deleted a few per write.
+pub fn record(
+    env: &Env,
+    token_id: u64,
+    action: ProvenanceAction,
+    from: Option<Address>,
+    to: Option<Address>,
+    operator: &Address,
+) {
+    let cap = get_cap(env);
+    if cap == 0 {
+        trim_to(env, token_id, 0, MAX_PROVENANCE_TRIM);
+        return;
+    }
+    let seq = recorded(env, token_id);
+    env.storage().persistent().set(
+        &DataKey::Provenance(token_id, seq),
+        &ProvenanceEntry {
+            action,
+            from,
+            to,
+            operator: operator.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProvenanceCount(token_id), &(seq + 1));
+    trim_to(env, token_id, cap, MAX_PROVENANCE_TRIM);
+}
+
+/// Retained entries for `token_id`, oldest first; `offset` counts from the
+/// oldest retained entry. Burned tokens keep their log.
+pub fn provenance(env: &Env, token_id: u64, offset: u32, limit: u32) -> Vec<ProvenanceEntry> {
+    let count = recorded(env, token_id);
+    let first = count.saturating_sub(get_cap(env)).saturating_add(offset);
+    let end = first.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+    let mut entries = Vec::new(env);
+    for seq in first..end {
+        if let Some(entry) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Provenance(token_id, seq))
+        {
+            entries.push_back(entry);
+        }
+    }
+    entries
+}
@@ -0,0 +1,165 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{DataKey, MAX_BATCH_SIZE, MAX_GUARDIANS, MIN_RECOVERY_DELAY};
+use crate::token;
+use crate::transfer;
+use crate::types::{RecoveryConfig, RecoveryRequest};
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn get_guardians(env: &Env, owner: &Address) -> Option<RecoveryConfig> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RecoveryConfig(owner.clone()))
+}
+
+pub fn get_request(env: &Env, owner: &Address) -> Option<RecoveryRequest> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Recovery(owner.clone()))
+}
+
+/// Opts `owner` into guardian recovery, or replaces their guardian set.
+/// An empty `guardians` list opts out. Any open request is dropped, since it
+/// was approved under the previous set.
+pub fn set_guardians(
+    env: &Env,
+    owner: &Address,
+    guardians: Vec<Address>,
+    threshold: u32,
+    delay: u64,
+) -> Result<(), ContractError> {
+    let key = DataKey::RecoveryConfig(owner.clone());
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Recovery(owner.clone()));
+
+    if guardians.is_empty() {
+        env.storage().persistent().remove(&key);
+        events::emit_guardians_updated(env, owner.clone(), 0, 0);
+        return Ok(());
+    }
+    if guardians.len() > MAX_GUARDIANS {
+        return Err(ContractError::BatchTooLarge);
+    }
+    if threshold == 0 || threshold > guardians.len() || delay < MIN_RECOVERY_DELAY {
+        return Err(ContractError::InvalidAmount);
+    }
+    for (i, guardian) in guardians.iter().enumerate() {
+        if &guardian == owner || guardians.first_index_of(&guardian) != Some(i as u32) {
+            return Err(ContractError::InvalidRecipient);
+        }
+    }
+
+    let count = guardians.len();
+    env.storage().persistent().set(
+        &key,
+        &RecoveryConfig {
+            guardians,
+            threshold,
+            delay,
+        },
+    );
+    events::emit_guardians_updated(env, owner.clone(), threshold, count);
+    Ok(())
+}
+
+/// Opens a request to move `owner`'s tokens to `new_owner`, counting as the
+/// first guardian approval.
+pub fn initiate(
+    env: &Env,
+    guardian: &Address,
+    owner: &Address,
+    new_owner: Address,
+) -> Result<(), ContractError> {
+    let config = get_guardians(env, owner).ok_or(ContractError::NotFound)?;
+    if !config.guardians.contains(guardian) {
+        return Err(ContractError::NotAuthorized);
+    }
+    if &new_owner == owner {
+        return Err(ContractError::InvalidRecipient);
+    }
+    if get_request(env, owner).is_some() {
+        return Err(ContractError::AlreadyApproved);
+    }
+
+    let request = RecoveryRequest {
+        new_owner: new_owner.clone(),
+        approvals: Vec::from_array(env, [guardian.clone()]),
+        ready_at: 0,
+    };
+    events::emit_recovery_initiated(env, owner.clone(), new_owner, guardian.clone());
+    save_approved(env, owner, &config, request);
+    Ok(())
+}
+
+pub fn approve(env: &Env, guardian: &Address, owner: &Address) -> Result<(), ContractError> {
+    let config = get_guardians(env, owner).ok_or(ContractError::NotFound)?;
+    if !config.guardians.contains(guardian) {
+        return Err(ContractError::NotAuthorized);
+    }
+    let mut request = get_request(env, owner).ok_or(ContractError::NotFound)?;
+    if request.approvals.contains(guardian) {
+        return Err(ContractError::AlreadyApproved);
+    }
+
+    request.approvals.push_back(guardian.clone());
+    save_approved(env, owner, &config, request);
+    Ok(())
+}
+
+/// Stores the request, starting the challenge delay once the threshold is met.
+fn save_approved(
+    env: &Env,
+    owner: &Address,
+    config: &RecoveryConfig,
+    mut request: RecoveryRequest,
+) {
+    let approvals = request.approvals.len();
+    if request.ready_at == 0 && approvals >= config.threshold {
+        request.ready_at = env.ledger().timestamp().saturating_add(config.delay);
+    }
+    let guardian = request.approvals.last().unwrap();
+    events::emit_recovery_approved(env, owner.clone(), guardian, approvals, request.ready_at);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Recovery(owner.clone()), &request);
+}
+
+/// Lets the original owner veto an open request at any point before it completes.
+pub fn cancel(env: &Env, owner: &Address) -> Result<(), ContractError> {
+    if get_request(env, owner).is_none() {
+        return Err(ContractError::NotFound);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Recovery(owner.clone()));
+    events::emit_recovery_cancelled(env, owner.clone());
+    Ok(())
+}
+
+/// Moves a batch of `owner`'s tokens to the approved new owner once the
+/// challenge delay has passed. Callable by anyone, since the outcome is fixed
+/// by the request; the request closes when the owner's balance reaches zero.
+pub fn execute(env: &Env, owner: &Address, token_ids: Vec<u64>) -> Result<u32, ContractError> {
+    let request = get_request(env, owner).ok_or(ContractError::NotFound)?;
+    if request.ready_at == 0 || env.ledger().timestamp() < request.ready_at {
+        return Err(ContractError::RecoveryNotReady);
+    }
+    let n = token_ids.len();
+    if n > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge);
+    }
+
+    let operator = env.current_contract_address();
+    for token_id in token_ids.iter() {
+        transfer::do_transfer(env, &operator, owner, &request.new_owner, token_id)?;
+    }
+    events::emit_recovery_executed(env, owner.clone(), request.new_owner, n);
+
+    if token::balance_of(env, owner) == 0 {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Recovery(owner.clone()));
+    }
+    Ok(n)
+}
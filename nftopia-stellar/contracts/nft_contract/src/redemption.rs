@@ -0,0 +1,184 @@
+use crate::NftContractClient;
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{DataKey, MAX_BATCH_SIZE};
+use crate::token;
+use crate::types::{Recipe, RecipeInput, RecipeOutput};
+use soroban_sdk::{Address, Env, Vec};
+
+pub fn get_recipe(env: &Env, recipe_id: u32) -> Option<Recipe> {
+    env.storage().persistent().get(&DataKey::Recipe(recipe_id))
+}
+
+fn input_count(input: &RecipeInput) -> u32 {
+    match input {
+        RecipeInput::Token(_) => 1,
+        RecipeInput::Attribute(_, _, count) | RecipeInput::Any(count) => *count,
+    }
+}
+
+pub fn create_recipe(
+    env: &Env,
+    caller: &Address,
+    inputs: Vec<RecipeInput>,
+    output: RecipeOutput,
+) -> Result<u32, ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+
+    let mut slots = 0u32;
+    for input in inputs.iter() {
+        let count = input_count(&input);
+        if count == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        slots = slots.saturating_add(count);
+    }
+    if slots == 0 || slots > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize);
+    }
+    match &output {
+        RecipeOutput::Mint(uri, _) if uri.is_empty() => return Err(ContractError::InvalidUri),
+        RecipeOutput::MintExternal(collection, uri, _) => {
+            // Soroban forbids re-entry, so self-mints must use `Mint`
+            if collection == &env.current_contract_address() {
+                return Err(ContractError::InvalidRecipient);
+            }
+            if uri.is_empty() {
+                return Err(ContractError::InvalidUri);
+            }
+        }
+        _ => {}
+    }
+
+    let recipe_id: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::RecipeCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::RecipeCount, &(recipe_id + 1));
+    env.storage().persistent().set(
+        &DataKey::Recipe(recipe_id),
+        &Recipe {
+            creator: caller.clone(),
+            inputs,
+            output,
+            active: true,
+            redeemed_count: 0,
+        },
+    );
+
+    events::emit_recipe_created(env, recipe_id, caller.clone());
+    Ok(recipe_id)
+}
+
+pub fn set_recipe_active(
+    env: &Env,
+    caller: &Address,
+    recipe_id: u32,
+    active: bool,
+) -> Result<(), ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+    let mut recipe = get_recipe(env, recipe_id).ok_or(ContractError::NotFound)?;
+    recipe.active = active;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Recipe(recipe_id), &recipe);
+    Ok(())
+}
+
+fn satisfies(env: &Env, input: &RecipeInput, token_id: u64) -> bool {
+    match input {
+        RecipeInput::Token(id) => *id == token_id,
+        RecipeInput::Attribute(trait_type, value, _) => token::load_metadata(env, token_id)
+            .is_some_and(|meta| {
+                meta.attributes
+                    .iter()
+                    .any(|a| &a.trait_type == trait_type && &a.value == value)
+            }),
+        RecipeInput::Any(_) => true,
+    }
+}
+
+/// Burns `token_ids` against a recipe and delivers its output to the redeemer.
+///
+/// Tokens fill the recipe's inputs in order: the first `count` IDs must
+/// satisfy the first input, the next ones the second, and so on, with no
+/// IDs left over. Burns use the regular `burn` cleanup, and any failure,
+/// including the output mint, reverts the whole redemption.
+///
+/// Returns the minted token ID, or `None` for receipt-only recipes.
+pub fn redeem(
+    env: &Env,
+    redeemer: &Address,
+    token_ids: Vec<u64>,
+    recipe_id: u32,
+) -> Result<Option<u64>, ContractError> {
+    if env
+        .storage()
+        .instance()
+        .get::<_, bool>(&DataKey::IsPaused)
+        .unwrap_or(false)
+    {
+        return Err(ContractError::ContractPaused);
+    }
+    let mut recipe = get_recipe(env, recipe_id).ok_or(ContractError::NotFound)?;
+    if !recipe.active {
+        return Err(ContractError::RecipeInactive);
+    }
+
+    let mut slot = 0u32;
+    for input in recipe.inputs.iter() {
+        for _ in 0..input_count(&input) {
+            let token_id = token_ids.get(slot).ok_or(ContractError::RecipeMismatch)?;
+            slot += 1;
+            if &token::owner_of(env, token_id)? != redeemer {
+                return Err(ContractError::NotOwner);
+            }
+            if !satisfies(env, &input, token_id) {
+                return Err(ContractError::RecipeMismatch);
+            }
+        }
+    }
+    if slot != token_ids.len() {
+        return Err(ContractError::RecipeMismatch);
+    }
+
+    // A repeated ID fails here with TokenNotFound, since its first burn removed it
+    for token_id in token_ids.iter() {
+        token::burn(env, redeemer, token_id)?;
+    }
+
+    let output_token_id = match recipe.output.clone() {
+        RecipeOutput::Mint(uri, attributes) => Some(token::mint_one(
+            env,
+            &recipe.creator,
+            redeemer,
+            uri,
+            attributes,
+            None,
+        )?),
+        RecipeOutput::MintExternal(collection, uri, attributes) => {
+            // This contract must hold MINTER on `collection`
+            let client = NftContractClient::new(env, &collection);
+            Some(client.mint(
+                &env.current_contract_address(),
+                redeemer,
+                &uri,
+                &attributes,
+                &None,
+            ))
+        }
+        RecipeOutput::Receipt => None,
+    };
+
+    recipe.redeemed_count += 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Recipe(recipe_id), &recipe);
+
+    events::emit_redeemed(env, recipe_id, redeemer.clone(), token_ids, output_token_id);
+    Ok(output_token_id)
+}
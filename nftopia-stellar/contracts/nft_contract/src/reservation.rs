@@ -0,0 +1,113 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::{DataKey, MAX_BATCH_SIZE, MAX_RESERVED_RANGES};
+use crate::types::ReservedRange;
+use soroban_sdk::{Address, Env, Vec};
+
+/// Reserved ranges, sorted by `start` and non-overlapping.
+pub fn get_ranges(env: &Env) -> Vec<ReservedRange> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReservedRanges)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn range_containing(ranges: &Vec<ReservedRange>, token_id: u64) -> Option<ReservedRange> {
+    ranges
+        .iter()
+        .find(|r| r.start <= token_id && token_id <= r.end)
+}
+
+pub fn reserve_range(
+    env: &Env,
+    caller: &Address,
+    start: u64,
+    end: u64,
+    minters: Vec<Address>,
+) -> Result<(), ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+    if start == 0 || end < start || end == u64::MAX {
+        return Err(ContractError::InvalidAmount);
+    }
+    if minters.is_empty() || minters.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatchSize);
+    }
+
+    let ranges = get_ranges(env);
+    if ranges.len() >= MAX_RESERVED_RANGES {
+        return Err(ContractError::BatchTooLarge);
+    }
+
+    let mut position = ranges.len();
+    for (i, range) in ranges.iter().enumerate() {
+        if start <= range.end && range.start <= end {
+            return Err(ContractError::TokenIdUnavailable);
+        }
+        if end < range.start && position == ranges.len() {
+            position = i as u32;
+        }
+    }
+
+    let mut updated = ranges;
+    updated.insert(
+        position,
+        ReservedRange {
+            start,
+            end,
+            minters,
+        },
+    );
+    env.storage()
+        .instance()
+        .set(&DataKey::ReservedRanges, &updated);
+
+    events::emit_range_reserved(env, start, end);
+    Ok(())
+}
+
+/// Lifts the minter restriction on the reservation starting at `start`.
+/// Released IDs are never handed out sequentially: a range the cursor has
+/// passed is dropped, and one still ahead stays skipped (some of its IDs may
+/// be taken) with no named minters, so any minter can fill it by ID.
+pub fn release_range(env: &Env, caller: &Address, start: u64) -> Result<(), ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+    let mut ranges = get_ranges(env);
+    let index = ranges
+        .iter()
+        .position(|r| r.start == start && !r.minters.is_empty())
+        .ok_or(ContractError::NotFound)? as u32;
+    let mut range = ranges.get(index).unwrap();
+    let cursor: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::NextTokenId)
+        .unwrap_or(1u64);
+    if range.end < cursor {
+        ranges.remove(index);
+    } else {
+        range.minters = Vec::new(env);
+        ranges.set(index, range.clone());
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::ReservedRanges, &ranges);
+
+    events::emit_range_released(env, range.start, range.end);
+    Ok(())
+}
+
+/// Drops released ranges that sequential allocation has moved past.
+pub fn prune_passed(env: &Env, ranges: Vec<ReservedRange>, cursor: u64) {
+    let mut kept = Vec::new(env);
+    for range in ranges.iter() {
+        if !(range.minters.is_empty() && range.end < cursor) {
+            kept.push_back(range);
+        }
+    }
+    if kept.len() != ranges.len() {
+        env.storage()
+            .instance()
+            .set(&DataKey::ReservedRanges, &kept);
+    }
+}
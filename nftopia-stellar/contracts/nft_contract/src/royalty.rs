@@ -1,8 +1,9 @@
 use crate::access_control;
 use crate::error::ContractError;
 use crate::events;
+use crate::multisig;
 use crate::storage::{DataKey, MAX_ROYALTY_BPS};
-use crate::types::RoyaltyInfo;
+use crate::types::{RoyaltyInfo, admin_action};
 use soroban_sdk::{Address, Env};
 
 pub fn set_default_royalty(
@@ -12,6 +13,15 @@ pub fn set_default_royalty(
     percentage: u32,
 ) -> Result<(), ContractError> {
     access_control::require_admin_or_owner(env, caller);
+    multisig::require_unprotected(env, admin_action::SET_DEFAULT_ROYALTY);
+    apply_default_royalty(env, recipient, percentage)
+}
+
+pub fn apply_default_royalty(
+    env: &Env,
+    recipient: Address,
+    percentage: u32,
+) -> Result<(), ContractError> {
     if percentage > MAX_ROYALTY_BPS {
         return Err(ContractError::InvalidRoyalty);
     }
@@ -34,6 +44,16 @@ pub fn set_token_royalty(
     percentage: u32,
 ) -> Result<(), ContractError> {
     access_control::require_admin_or_owner(env, caller);
+    multisig::require_unprotected(env, admin_action::SET_TOKEN_ROYALTY);
+    apply_token_royalty(env, token_id, recipient, percentage)
+}
+
+pub fn apply_token_royalty(
+    env: &Env,
+    token_id: u64,
+    recipient: Address,
+    percentage: u32,
+) -> Result<(), ContractError> {
     if percentage > MAX_ROYALTY_BPS {
         return Err(ContractError::InvalidRoyalty);
     }
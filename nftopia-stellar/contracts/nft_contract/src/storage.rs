@@ -11,15 +11,34 @@ pub enum DataKey {
     IsPaused,
     MetadataFrozen,
     BaseUri,
+    ContractUri,
+    BurnedCount,
+    HolderCount,
+    TransferRules,
+    ReservedRanges,
+    ProvenanceCap,
 
-    // Per-token data
-    TokenData(u64),
-    TokenOwner(u64),
-    TokenApproved(u64),
+    // Per-token data: hot ownership record apart from cold metadata
+    Token(u64),
+    TokenMeta(u64),
+    TokenMedia(u64), // Only written for tokens that carry media fields
+    ProvenanceCount(u64),
+    ProvenanceFirst(u64), // Oldest sequence still stored
+    Provenance(u64, u32), // (token_id, sequence)
+    PendingClaim(u64),
+    TokenLock(u64),
+    HeldSince(u64),
 
     // Per-address data
     Balance(Address),
     OperatorApproval(Address, Address), // (owner, operator)
+    RuleExempt(Address),
+    LockerTokenCount(Address),
+    LockerToken(Address, u32), // (locker, index)
+    Holder(u32),               // index into the holder list
+    HolderIndex(Address),
+    RecoveryConfig(Address),
+    Recovery(Address),
 
     // Role-based access control
     Role(Address, u32), // (address, role_discriminant)
@@ -28,12 +47,61 @@ pub enum DataKey {
     DefaultRoyalty,
     TokenRoyalty(u64),
 
+    // Governance snapshots
+    Checkpoint(CheckpointSeries, u32), // (series, index)
+    CheckpointCount(CheckpointSeries),
+    Delegate(Address),
+
+    // M-of-N admin proposals
+    Multisig(MultisigKey),
+
+    // Merkle airdrops
+    AirdropCount,
+    Airdrop(u32),
+    AirdropClaimed(u32, u32), // (campaign_id, bitmap word index)
+
+    // Burn-to-redeem recipes
+    RecipeCount,
+    Recipe(u32),
+
+    // Legacy per-token layout, read only by `migrate_token_storage`
+    TokenData(u64),
+    TokenOwner(u64),
+    TokenApproved(u64),
+
     // Rate limiting: tracks last batch timestamp per caller
     LastBatchTime(Address),
     BatchCount(Address),
 }
 
+/// Identifies one checkpointed value history.
+#[derive(Clone)]
+#[contracttype]
+pub enum CheckpointSeries {
+    Balance(Address),
+    Votes(Address),
+    TotalSupply,
+}
+
+/// Keys for multi-signature admin proposals, grouped under `DataKey::Multisig`.
+#[derive(Clone)]
+#[contracttype]
+pub enum MultisigKey {
+    Config,
+    ProposalCount,
+    Proposal(u32),
+}
+
 pub const MAX_BATCH_SIZE: u32 = 50;
 pub const BATCH_RATE_WINDOW: u64 = 100; // ledger sequences
 pub const MAX_ROYALTY_BPS: u32 = 10_000; // 100%
 pub const MAX_SUPPLY_HARD_CAP: u64 = 1_000_000;
+pub const MAX_MERKLE_PROOF_DEPTH: u32 = 32;
+pub const MAX_PAGE_SIZE: u32 = 100;
+pub const DEFAULT_PROVENANCE_CAP: u32 = 20;
+pub const MAX_PROVENANCE_CAP: u32 = 100;
+pub const MAX_PROVENANCE_TRIM: u32 = 10; // rolled-off entries deleted per write
+pub const MAX_HOLDER_PAGE: u32 = 50; // two reads per holder: address and balance
+pub const MAX_RESERVED_RANGES: u32 = 20;
+pub const MAX_GUARDIANS: u32 = 10;
+pub const MIN_RECOVERY_DELAY: u64 = 86_400; // seconds
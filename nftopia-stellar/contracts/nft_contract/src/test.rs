@@ -1,6 +1,15 @@
-use crate::types::{CollectionConfig, RoyaltyInfo, TokenAttribute};
+use crate::error::ContractError;
+use crate::storage::DataKey;
+use crate::types::{
+    AdminCall, AirdropLeaf, CollectionConfig, MultisigConfig, ProvenanceAction, RecipeInput,
+    RecipeOutput, RoyaltyInfo, TokenAttribute, TokenData, TokenMedia, TokenMetadata,
+    TokenOwnership, admin_action, role,
+};
 use crate::{NftContract, NftContractClient};
-use soroban_sdk::{Address, Env, String, Vec, testutils::Address as _};
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env, Map, String, Symbol, TryFromVal, Val, Vec, contract, contractimpl,
+    testutils::{Address as _, Events, Ledger},
+};
 
 fn make_config(env: &Env) -> CollectionConfig {
     CollectionConfig {
@@ -22,7 +31,7 @@ fn setup(env: &Env) -> (NftContractClient<'_>, Address) {
         recipient: admin.clone(),
         percentage: 500,
     };
-    client.initialize(&admin, &make_config(env), &Some(royalty));
+    client.initialize(&admin, &make_config(env), &Some(royalty), &None);
     (client, admin)
 }
 
@@ -263,7 +272,7 @@ fn test_supply_limit() {
         is_revealed: true,
         metadata_is_frozen: false,
     };
-    client.initialize(&admin, &config, &None);
+    client.initialize(&admin, &config, &None, &None);
 
     let user = Address::generate(&env);
     client.mint(
@@ -737,3 +746,1812 @@ fn test_batch_burn_with_non_existent_token_fails() {
     assert_eq!(client.total_supply(), 1);
     assert_eq!(client.owner_of(&id1), owner);
 }
+
+// ─── Contract URI & collection info ──────────────────────────────────────────
+
+#[test]
+fn test_set_contract_uri_by_metadata_updater() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let updater = Address::generate(&env);
+    client.grant_role(&admin, &updater, &crate::types::role::METADATA_UPDATER);
+
+    assert_eq!(client.contract_uri(), None);
+    let uri = String::from_str(&env, "ipfs://collection.json");
+    client.set_contract_uri(&updater, &uri);
+    assert_eq!(client.contract_uri(), Some(uri));
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_clear_contract_uri(&stranger).is_err());
+    client.clear_contract_uri(&updater);
+    assert_eq!(client.contract_uri(), None);
+    assert_eq!(client.collection_info().contract_uri, None);
+}
+
+#[test]
+fn test_set_contract_uri_unauthorized_or_frozen_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let stranger = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://collection.json");
+    assert!(client.try_set_contract_uri(&stranger, &uri).is_err());
+
+    client.freeze_metadata(&admin);
+    assert!(client.try_set_contract_uri(&admin, &uri).is_err());
+}
+
+#[test]
+fn test_collection_info_tracks_supply_and_holders() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    let id1 = client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+    let id2 = client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &user2, &uri, &Vec::new(&env), &None);
+
+    let info = client.collection_info();
+    assert_eq!(info.total_supply, 3);
+    assert_eq!(info.holder_count, 2);
+    assert_eq!(info.next_token_id, 4);
+
+    // user1 -> user2 leaves user1 with one token; burning it drops user1 as a holder
+    client.transfer(&user1, &user1, &user2, &id1);
+    assert_eq!(client.collection_info().holder_count, 2);
+    client.burn(&user1, &id2);
+
+    client.set_pause(&admin, &true);
+    let info = client.collection_info();
+    assert_eq!(info.total_supply, 2);
+    assert_eq!(info.burned_count, 1);
+    assert_eq!(info.holder_count, 1);
+    assert!(info.is_paused);
+    assert!(!info.metadata_frozen);
+    assert_eq!(info.config.symbol, String::from_str(&env, "NFTP"));
+    assert_eq!(info.royalty_recipient, Some(admin));
+    assert_eq!(info.royalty_percentage, 500);
+}
+
+// ─── Governance checkpoints & delegation ─────────────────────────────────────
+
+#[test]
+fn test_balance_and_supply_checkpoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    env.ledger().set_sequence_number(10);
+    let id1 = client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+
+    env.ledger().set_sequence_number(20);
+    client.transfer(&user1, &user1, &user2, &id1);
+
+    env.ledger().set_sequence_number(30);
+    client.burn(&user2, &id1);
+
+    // Ledger 30 is still open, so its final values are not known yet
+    assert!(client.try_total_supply_at(&30).is_err());
+    assert!(client.try_balance_of_at(&user2, &30).is_err());
+
+    env.ledger().set_sequence_number(31);
+    assert_eq!(client.balance_of_at(&user1, &9), 0);
+    assert_eq!(client.balance_of_at(&user1, &10), 2);
+    assert_eq!(client.balance_of_at(&user1, &15), 2);
+    assert_eq!(client.balance_of_at(&user1, &20), 1);
+    assert_eq!(client.balance_of_at(&user2, &25), 1);
+    assert_eq!(client.balance_of_at(&user2, &30), 0);
+
+    assert_eq!(client.total_supply_at(&5), 0);
+    assert_eq!(client.total_supply_at(&10), 2);
+    assert_eq!(client.total_supply_at(&29), 2);
+    assert_eq!(client.total_supply_at(&30), 1);
+
+    // Snapshots for ledgers that have not closed yet are rejected
+    assert!(client.try_total_supply_at(&31).is_err());
+    assert!(client.try_total_supply_at(&32).is_err());
+}
+
+#[test]
+fn test_delegate_moves_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let holder = Address::generate(&env);
+    let delegatee = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    env.ledger().set_sequence_number(100);
+    client.mint(&admin, &holder, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &holder, &uri, &Vec::new(&env), &None);
+    assert_eq!(client.votes_of(&holder), 2);
+    assert_eq!(client.get_delegate(&holder), holder);
+
+    env.ledger().set_sequence_number(110);
+    client.delegate(&holder, &delegatee);
+    assert_eq!(client.votes_of(&holder), 0);
+    assert_eq!(client.votes_of(&delegatee), 2);
+
+    // New tokens follow the current delegation
+    env.ledger().set_sequence_number(120);
+    client.mint(&admin, &holder, &uri, &Vec::new(&env), &None);
+    assert_eq!(client.votes_of(&delegatee), 3);
+
+    assert_eq!(client.votes_of_at(&holder, &105), 2);
+    assert_eq!(client.votes_of_at(&delegatee, &105), 0);
+    assert_eq!(client.votes_of_at(&delegatee, &115), 2);
+
+    // Self-delegation takes the votes back
+    client.delegate(&holder, &holder);
+    assert_eq!(client.votes_of(&holder), 3);
+    assert_eq!(client.votes_of(&delegatee), 0);
+}
+
+// ─── Claimable transfers ─────────────────────────────────────────────────────
+
+fn claim_hash(env: &Env, preimage: &Bytes) -> BytesN<32> {
+    env.crypto().sha256(preimage).to_bytes()
+}
+
+#[test]
+fn test_transfer_to_claim_and_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let gifter = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_id = client.mint(
+        &admin,
+        &gifter,
+        &String::from_str(&env, "ipfs://gift"),
+        &Vec::new(&env),
+        &None,
+    );
+
+    let secret = Bytes::from_slice(&env, b"welcome-to-nftopia");
+    client.transfer_to_claim(&gifter, &token_id, &claim_hash(&env, &secret), &1_000);
+
+    // While pending, the token sits in contract escrow
+    assert_eq!(client.owner_of(&token_id), client.address.clone());
+    assert_eq!(client.balance_of(&gifter), 0);
+    assert_eq!(client.balance_of(&client.address), 1);
+    assert_eq!(client.pending_claim(&token_id).unwrap().sender, gifter);
+
+    let wrong = Bytes::from_slice(&env, b"guess");
+    assert!(client.try_claim(&token_id, &wrong, &recipient).is_err());
+
+    client.claim(&token_id, &secret, &recipient);
+    assert_eq!(client.owner_of(&token_id), recipient.clone());
+    assert_eq!(client.balance_of(&recipient), 1);
+    assert_eq!(client.balance_of(&client.address), 0);
+    assert!(client.pending_claim(&token_id).is_none());
+}
+
+#[test]
+fn test_claim_escrow_is_not_a_holder_or_voter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let gifter = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://gift");
+    let token_id = client.mint(&admin, &gifter, &uri, &Vec::new(&env), &None);
+    let secret = Bytes::from_slice(&env, b"welcome-to-nftopia");
+    client.transfer_to_claim(&gifter, &token_id, &claim_hash(&env, &secret), &1_000);
+
+    assert_eq!(client.holders(&0, &10).len(), 0);
+    assert_eq!(client.votes_of(&client.address), 0);
+    assert_eq!(client.votes_of(&gifter), 0);
+
+    client.claim(&token_id, &secret, &recipient);
+    assert_eq!(
+        client.holders(&0, &10),
+        Vec::from_array(&env, [(recipient.clone(), 1u64)])
+    );
+    assert_eq!(client.votes_of(&recipient), 1);
+    assert_eq!(client.votes_of(&client.address), 0);
+}
+
+#[test]
+fn test_expired_claim_is_reclaimed_by_sender() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let gifter = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let token_id = client.mint(
+        &admin,
+        &gifter,
+        &String::from_str(&env, "ipfs://gift"),
+        &Vec::new(&env),
+        &None,
+    );
+
+    let secret = Bytes::from_slice(&env, b"late");
+    env.ledger().set_timestamp(100);
+    client.transfer_to_claim(&gifter, &token_id, &claim_hash(&env, &secret), &200);
+
+    // Sender cannot pull the token back before expiry
+    assert!(client.try_reclaim(&gifter, &token_id).is_err());
+
+    env.ledger().set_timestamp(201);
+    assert!(client.try_claim(&token_id, &secret, &stranger).is_err());
+    assert!(client.try_reclaim(&stranger, &token_id).is_err());
+
+    client.reclaim(&gifter, &token_id);
+    assert_eq!(client.owner_of(&token_id), gifter.clone());
+    assert_eq!(client.balance_of(&gifter), 1);
+    assert!(client.pending_claim(&token_id).is_none());
+}
+
+// ─── Merkle airdrops ─────────────────────────────────────────────────────────
+
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut buf = Bytes::from_array(env, &first.to_array());
+    buf.append(&Bytes::from_array(env, &second.to_array()));
+    env.crypto().sha256(&buf).to_bytes()
+}
+
+/// Builds a four-leaf tree and returns (root, leaves, proofs).
+fn build_airdrop(
+    env: &Env,
+    recipients: &[Address; 4],
+) -> (BytesN<32>, Vec<AirdropLeaf>, Vec<Vec<BytesN<32>>>) {
+    let attributes_hash = crate::airdrop::hash_attributes(env, &Vec::new(env));
+    let mut leaves: Vec<AirdropLeaf> = Vec::new(env);
+    let mut hashes: Vec<BytesN<32>> = Vec::new(env);
+    for (i, recipient) in recipients.iter().enumerate() {
+        let leaf = AirdropLeaf {
+            index: i as u32,
+            recipient: recipient.clone(),
+            metadata_uri: String::from_str(env, "ipfs://drop"),
+            attributes_hash: attributes_hash.clone(),
+        };
+        hashes.push_back(crate::airdrop::hash_leaf(env, &leaf));
+        leaves.push_back(leaf);
+    }
+
+    let left = hash_pair(env, &hashes.get(0).unwrap(), &hashes.get(1).unwrap());
+    let right = hash_pair(env, &hashes.get(2).unwrap(), &hashes.get(3).unwrap());
+    let root = hash_pair(env, &left, &right);
+
+    let mut proofs: Vec<Vec<BytesN<32>>> = Vec::new(env);
+    for i in 0..4u32 {
+        let sibling = hashes.get(i ^ 1).unwrap();
+        let uncle = if i < 2 { right.clone() } else { left.clone() };
+        proofs.push_back(Vec::from_array(env, [sibling, uncle]));
+    }
+    (root, leaves, proofs)
+}
+
+#[test]
+fn test_claim_airdrop_with_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let recipients = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let (root, leaves, proofs) = build_airdrop(&env, &recipients);
+    let campaign_id = client.create_airdrop(&admin, &root, &4, &1_000);
+
+    let token_id = client.claim_airdrop(
+        &campaign_id,
+        &leaves.get(2).unwrap(),
+        &Vec::new(&env),
+        &proofs.get(2).unwrap(),
+    );
+    assert_eq!(client.owner_of(&token_id), recipients[2].clone());
+    assert!(client.is_airdrop_claimed(&campaign_id, &2));
+    assert!(!client.is_airdrop_claimed(&campaign_id, &1));
+    assert_eq!(client.get_airdrop(&campaign_id).unwrap().claimed_count, 1);
+
+    // Double claims and mismatched proofs are rejected
+    let result = client.try_claim_airdrop(
+        &campaign_id,
+        &leaves.get(2).unwrap(),
+        &Vec::new(&env),
+        &proofs.get(2).unwrap(),
+    );
+    assert!(result.is_err());
+    let result = client.try_claim_airdrop(
+        &campaign_id,
+        &leaves.get(0).unwrap(),
+        &Vec::new(&env),
+        &proofs.get(1).unwrap(),
+    );
+    assert!(result.is_err());
+
+    // Tampered metadata no longer matches the published leaf
+    let mut forged = leaves.get(0).unwrap();
+    forged.metadata_uri = String::from_str(&env, "ipfs://rare");
+    let result = client.try_claim_airdrop(
+        &campaign_id,
+        &forged,
+        &Vec::new(&env),
+        &proofs.get(0).unwrap(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_sweep_unclaimed_airdrop_after_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let recipients = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let treasury = Address::generate(&env);
+    let (root, leaves, proofs) = build_airdrop(&env, &recipients);
+
+    env.ledger().set_timestamp(100);
+    let campaign_id = client.create_airdrop(&admin, &root, &4, &500);
+    client.claim_airdrop(
+        &campaign_id,
+        &leaves.get(0).unwrap(),
+        &Vec::new(&env),
+        &proofs.get(0).unwrap(),
+    );
+
+    let mut rest: Vec<AirdropLeaf> = Vec::new(&env);
+    let mut rest_attrs: Vec<Vec<TokenAttribute>> = Vec::new(&env);
+    let mut rest_proofs: Vec<Vec<BytesN<32>>> = Vec::new(&env);
+    for i in 1..4u32 {
+        rest.push_back(leaves.get(i).unwrap());
+        rest_attrs.push_back(Vec::new(&env));
+        rest_proofs.push_back(proofs.get(i).unwrap());
+    }
+
+    // Sweeping is only possible once the campaign has expired
+    let result = client.try_sweep_airdrop(
+        &admin,
+        &campaign_id,
+        &treasury,
+        &rest,
+        &rest_attrs,
+        &rest_proofs,
+    );
+    assert!(result.is_err());
+
+    env.ledger().set_timestamp(501);
+    let result = client.try_claim_airdrop(
+        &campaign_id,
+        &leaves.get(1).unwrap(),
+        &Vec::new(&env),
+        &proofs.get(1).unwrap(),
+    );
+    assert!(result.is_err());
+
+    let ids = client.sweep_airdrop(
+        &admin,
+        &campaign_id,
+        &treasury,
+        &rest,
+        &rest_attrs,
+        &rest_proofs,
+    );
+    assert_eq!(ids.len(), 3);
+    assert_eq!(client.balance_of(&treasury), 3);
+    assert_eq!(client.get_airdrop(&campaign_id).unwrap().claimed_count, 4);
+}
+
+// ─── Token locks ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_locked_token_rejects_transfer_burn_and_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let locker = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    client.grant_role(&admin, &locker, &crate::types::role::LOCKER);
+
+    let token_id = client.mint(
+        &admin,
+        &owner,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+    );
+
+    // Only LOCKER role holders can hold locks
+    assert!(client.try_lock(&owner, &token_id, &buyer, &1_000).is_err());
+
+    client.lock(&owner, &token_id, &locker, &1_000);
+    assert!(client.is_locked(&token_id));
+    assert_eq!(client.get_lock(&token_id).unwrap().locker, locker);
+    assert_eq!(client.locked_tokens(&locker, &0, &10).len(), 1);
+
+    assert!(
+        client
+            .try_transfer(&owner, &owner, &buyer, &token_id)
+            .is_err()
+    );
+    assert!(client.try_burn(&owner, &token_id).is_err());
+    assert!(client.try_approve(&owner, &buyer, &token_id).is_err());
+    assert!(client.try_lock(&owner, &token_id, &locker, &2_000).is_err());
+
+    // Owner cannot unlock before unlock_at
+    assert!(client.try_unlock(&owner, &token_id).is_err());
+    env.ledger().set_timestamp(1_000);
+    client.unlock(&owner, &token_id);
+    assert!(!client.is_locked(&token_id));
+    assert_eq!(client.locked_tokens(&locker, &0, &10).len(), 0);
+
+    client.transfer(&owner, &owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+}
+
+#[test]
+fn test_lock_requires_locker_consent() {
+    use soroban_sdk::{
+        IntoVal,
+        testutils::{MockAuth, MockAuthInvoke},
+    };
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let locker = Address::generate(&env);
+    client.grant_role(&admin, &locker, &crate::types::role::LOCKER);
+    let token_id = client.mint(
+        &admin,
+        &owner,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+    );
+
+    // Only the owner signs: the named locker never agreed
+    let args = (owner.clone(), token_id, locker.clone(), 1_000u64).into_val(&env);
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &owner,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "lock",
+                args,
+                sub_invokes: &[],
+            },
+        }])
+        .try_lock(&owner, &token_id, &locker, &1_000);
+    assert!(result.is_err());
+    assert!(!client.is_locked(&token_id));
+}
+
+#[test]
+fn test_locker_settles_by_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let seller = Address::generate(&env);
+    let locker = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    client.grant_role(&admin, &locker, &crate::types::role::LOCKER);
+
+    let mut ids: Vec<u64> = Vec::new(&env);
+    for _ in 0..3u32 {
+        let id = client.mint(
+            &admin,
+            &seller,
+            &String::from_str(&env, "ipfs://hash"),
+            &Vec::new(&env),
+            &None,
+        );
+        client.lock(&seller, &id, &locker, &1_000);
+        ids.push_back(id);
+    }
+    assert_eq!(client.locked_tokens(&locker, &0, &10).len(), 3);
+
+    // The locker moves the first token without an approval, releasing its lock
+    let sold = ids.get(0).unwrap();
+    client.transfer(&locker, &seller, &buyer, &sold);
+    assert_eq!(client.owner_of(&sold), buyer.clone());
+    assert!(!client.is_locked(&sold));
+
+    let remaining = client.locked_tokens(&locker, &0, &10);
+    assert_eq!(remaining.len(), 2);
+    assert!(remaining.contains(ids.get(1).unwrap()));
+    assert!(remaining.contains(ids.get(2).unwrap()));
+    assert_eq!(client.get_lock(&ids.get(2).unwrap()).unwrap().index, 0);
+}
+
+// ─── Wallet caps & transfer cooldowns ────────────────────────────────────────
+
+#[test]
+fn test_max_per_wallet_enforced_on_mint_and_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let whale = Address::generate(&env);
+    let other = Address::generate(&env);
+    let escrow = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    client.set_transfer_rules(&admin, &Some(2), &0);
+
+    client.mint(&admin, &whale, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &whale, &uri, &Vec::new(&env), &None);
+    assert!(
+        client
+            .try_mint(&admin, &whale, &uri, &Vec::new(&env), &None)
+            .is_err()
+    );
+
+    let id = client.mint(&admin, &other, &uri, &Vec::new(&env), &None);
+    assert!(client.try_transfer(&other, &other, &whale, &id).is_err());
+
+    // Exempt addresses (e.g. marketplace escrow) may hold any amount
+    client.set_rule_exemption(&admin, &escrow, &true);
+    assert!(client.is_rule_exempt(&escrow));
+    for _ in 0..3u32 {
+        client.mint(&admin, &escrow, &uri, &Vec::new(&env), &None);
+    }
+    assert_eq!(client.balance_of(&escrow), 3);
+}
+
+#[test]
+fn test_transfer_cooldown_after_receipt() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    client.set_transfer_rules(&admin, &None, &3_600);
+
+    env.ledger().set_timestamp(1_000);
+    let id = client.mint(
+        &admin,
+        &user1,
+        &String::from_str(&env, "ipfs://hash"),
+        &Vec::new(&env),
+        &None,
+    );
+    assert!(client.try_transfer(&user1, &user1, &user2, &id).is_err());
+
+    env.ledger().set_timestamp(4_600);
+    client.transfer(&user1, &user1, &user2, &id);
+
+    // The holding period restarts for the new owner
+    env.ledger().set_timestamp(5_000);
+    assert!(client.try_transfer(&user2, &user2, &user3, &id).is_err());
+
+    client.set_rule_exemption(&admin, &user2, &true);
+    client.transfer(&user2, &user2, &user3, &id);
+    assert_eq!(client.owner_of(&id), user3);
+}
+
+// ─── Explicit IDs & reserved ranges ──────────────────────────────────────────
+
+#[test]
+fn test_mint_with_id_and_collision() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let artist = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://one-of-one");
+    assert_eq!(
+        client.mint_with_id(&admin, &artist, &7, &uri, &Vec::new(&env)),
+        7
+    );
+    assert_eq!(client.owner_of(&7), artist.clone());
+
+    let result = client.try_mint_with_id(&admin, &artist, &7, &uri, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ContractError::TokenAlreadyExists)));
+
+    // Non-minters cannot pick IDs outside reserved ranges
+    let result = client.try_mint_with_id(&artist, &artist, &8, &uri, &Vec::new(&env));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_mint_with_id_at_u64_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let artist = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://edge");
+    let result = client.try_mint_with_id(&admin, &artist, &u64::MAX, &uri, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ContractError::ArithmeticError)));
+
+    // The last usable ID leaves no room for sequential allocation
+    client.mint_with_id(&admin, &artist, &(u64::MAX - 1), &uri, &Vec::new(&env));
+    let result = client.try_mint(&admin, &artist, &uri, &Vec::new(&env), &None);
+    assert_eq!(result, Err(Ok(ContractError::ArithmeticError)));
+    assert_eq!(client.balance_of(&artist), 1);
+}
+
+#[test]
+fn test_sequential_mint_skips_reserved_and_taken_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let team = Address::generate(&env);
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    client.reserve_range(&admin, &1, &3, &Vec::from_array(&env, [team.clone()]));
+    client.mint_with_id(&admin, &user, &4, &uri, &Vec::new(&env));
+    client.mint_with_id(&admin, &user, &5, &uri, &Vec::new(&env));
+
+    // 1..=3 are reserved and 4, 5 are taken
+    assert_eq!(client.mint(&admin, &user, &uri, &Vec::new(&env), &None), 6);
+    assert_eq!(client.mint(&admin, &user, &uri, &Vec::new(&env), &None), 7);
+
+    // Only the named team address may mint into the reservation
+    let result = client.try_mint_with_id(&admin, &user, &2, &uri, &Vec::new(&env));
+    assert!(result.is_err());
+    client.mint_with_id(&team, &team, &2, &uri, &Vec::new(&env));
+    assert_eq!(client.owner_of(&2), team);
+
+    // Overlapping reservations are rejected
+    let result = client.try_reserve_range(&admin, &3, &10, &Vec::from_array(&env, [team.clone()]));
+    assert!(result.is_err());
+
+    client.release_range(&admin, &1);
+    assert_eq!(client.reserved_ranges().len(), 0);
+}
+
+#[test]
+fn test_explicit_ids_ahead_of_cursor_never_block_sequential_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    // A long run of explicit IDs just ahead of the cursor
+    for id in 1..=150u64 {
+        client.mint_with_id(&admin, &user, &id, &uri, &Vec::new(&env));
+    }
+    assert_eq!(
+        client.mint(&admin, &user, &uri, &Vec::new(&env), &None),
+        151
+    );
+
+    // Jumping far ahead skips the gap for sequential minting only
+    client.mint_with_id(&admin, &user, &500, &uri, &Vec::new(&env));
+    assert_eq!(
+        client.mint(&admin, &user, &uri, &Vec::new(&env), &None),
+        501
+    );
+    client.mint_with_id(&admin, &user, &300, &uri, &Vec::new(&env));
+    assert_eq!(client.owner_of(&300), user.clone());
+}
+
+#[test]
+fn test_released_range_ahead_of_cursor_stays_skipped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let team = Address::generate(&env);
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    client.reserve_range(&admin, &3, &5, &Vec::from_array(&env, [team.clone()]));
+    client.mint_with_id(&team, &team, &3, &uri, &Vec::new(&env));
+    client.release_range(&admin, &3);
+
+    // The released range is kept, open to any minter, and still skipped
+    let ranges = client.reserved_ranges();
+    assert_eq!(ranges.len(), 1);
+    assert!(ranges.get(0).unwrap().minters.is_empty());
+    client.mint_with_id(&admin, &user, &4, &uri, &Vec::new(&env));
+
+    assert_eq!(client.mint(&admin, &user, &uri, &Vec::new(&env), &None), 1);
+    assert_eq!(client.mint(&admin, &user, &uri, &Vec::new(&env), &None), 2);
+    assert_eq!(client.mint(&admin, &user, &uri, &Vec::new(&env), &None), 6);
+
+    // Once the cursor has passed it, the released range is dropped
+    assert_eq!(client.reserved_ranges().len(), 0);
+}
+
+// ─── Storage layout ──────────────────────────────────────────────────────────
+
+fn heavy_attributes(env: &Env) -> Vec<TokenAttribute> {
+    let mut attrs = Vec::new(env);
+    for _ in 0..10 {
+        attrs.push_back(TokenAttribute {
+            trait_type: String::from_str(env, "Background pattern and palette"),
+            value: String::from_str(env, "Deep ultramarine with gold leaf accents"),
+            display_type: Some(String::from_str(env, "string")),
+        });
+    }
+    attrs
+}
+
+#[test]
+fn test_transfer_cost_independent_of_metadata_size() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let light_uri = String::from_str(&env, "ipfs://a");
+    let heavy_uri = String::from_str(
+        &env,
+        "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG/metadata/collection/0001.json",
+    );
+
+    // Warm up holder and balance entries so the measured calls match
+    client.mint(&admin, &user1, &light_uri, &Vec::new(&env), &None);
+    client.mint(&admin, &user2, &light_uri, &Vec::new(&env), &None);
+    let light = client.mint(&admin, &user1, &light_uri, &Vec::new(&env), &None);
+    let light_mint = env.cost_estimate().resources();
+    let heavy = client.mint(&admin, &user1, &heavy_uri, &heavy_attributes(&env), &None);
+    let heavy_mint = env.cost_estimate().resources();
+
+    // Metadata is the only difference at mint, written once to its own entry
+    assert_eq!(light_mint.write_entries, heavy_mint.write_entries);
+    assert!(heavy_mint.write_bytes > light_mint.write_bytes);
+
+    // Transfers only touch the ownership entry, never the metadata entry
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    client.transfer(&user1, &user1, &user2, &light);
+    let light_transfer = env.cost_estimate().resources();
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    client.transfer(&user1, &user1, &user2, &heavy);
+    let heavy_transfer = env.cost_estimate().resources();
+
+    assert_eq!(light_transfer.write_entries, heavy_transfer.write_entries);
+    assert_eq!(light_transfer.write_bytes, heavy_transfer.write_bytes);
+    assert_eq!(
+        light_transfer.memory_read_entries,
+        heavy_transfer.memory_read_entries
+    );
+    assert!(heavy_transfer.write_bytes < heavy_mint.write_bytes);
+    assert_eq!(client.token_metadata(&heavy).owner, user2);
+}
+
+#[test]
+fn test_clearing_approval_on_transfer_adds_no_write() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://token");
+    // Warm up the recipient's balance and holder entries for both transfers
+    client.mint(&admin, &user2, &uri, &Vec::new(&env), &None);
+    let plain = client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+    let approved = client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &user1, &uri, &Vec::new(&env), &None);
+    client.approve(&user1, &operator, &approved);
+
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    client.transfer(&user1, &user1, &user2, &plain);
+    let plain_transfer = env.cost_estimate().resources();
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    client.transfer(&operator, &user1, &user2, &approved);
+    let approved_transfer = env.cost_estimate().resources();
+
+    // The approval lives in the ownership entry, so clearing it is free
+    assert_eq!(
+        plain_transfer.write_entries,
+        approved_transfer.write_entries
+    );
+    assert_eq!(plain_transfer.write_bytes, approved_transfer.write_bytes);
+    assert_eq!(client.get_approved(&approved), None);
+}
+
+/// Per-token reads and writes of the pre-split layout, on the legacy keys
+/// kept for `migrate_token_storage`.
+#[contract]
+struct LegacyLayout;
+
+#[contractimpl]
+impl LegacyLayout {
+    pub fn mint(env: Env, data: TokenData) {
+        let store = env.storage().persistent();
+        store.set(&DataKey::TokenData(data.id), &data);
+        store.set(&DataKey::TokenOwner(data.id), &data.owner);
+    }
+
+    pub fn approve(env: Env, token_id: u64, approved: Address) {
+        let store = env.storage().persistent();
+        let _: Address = store.get(&DataKey::TokenOwner(token_id)).unwrap();
+        store.set(&DataKey::TokenApproved(token_id), &approved);
+    }
+
+    pub fn transfer(env: Env, token_id: u64, to: Address) {
+        let store = env.storage().persistent();
+        let _: Address = store.get(&DataKey::TokenOwner(token_id)).unwrap();
+        let _: Option<Address> = store.get(&DataKey::TokenApproved(token_id));
+        store.remove(&DataKey::TokenApproved(token_id));
+        store.set(&DataKey::TokenOwner(token_id), &to);
+    }
+}
+
+/// The same operations through the split ownership and metadata records.
+#[contract]
+struct SplitLayout;
+
+#[contractimpl]
+impl SplitLayout {
+    pub fn mint(env: Env, data: TokenData) {
+        crate::token::save_ownership(&env, data.id, &TokenOwnership(data.owner, None));
+        crate::token::save_metadata(
+            &env,
+            data.id,
+            &TokenMetadata {
+                metadata_uri: data.metadata_uri,
+                created_at: data.created_at,
+                creator: data.creator,
+                royalty_percentage: data.royalty_percentage,
+                royalty_recipient: data.royalty_recipient,
+                attributes: data.attributes,
+                edition_number: data.edition_number,
+                total_editions: data.total_editions,
+            },
+        );
+    }
+
+    pub fn approve(env: Env, token_id: u64, approved: Address) {
+        let ownership = crate::token::load_ownership(&env, token_id).unwrap();
+        crate::token::save_ownership(&env, token_id, &TokenOwnership(ownership.0, Some(approved)));
+    }
+
+    pub fn transfer(env: Env, token_id: u64, to: Address) {
+        let _ = crate::token::load_ownership(&env, token_id).unwrap();
+        crate::token::save_ownership(&env, token_id, &TokenOwnership(to, None));
+    }
+}
+
+#[test]
+fn test_split_layout_footprint_against_legacy() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let data = TokenData {
+        id: 1,
+        owner: owner.clone(),
+        metadata_uri: String::from_str(
+            &env,
+            "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        ),
+        created_at: 0,
+        creator: owner.clone(),
+        royalty_percentage: 500,
+        royalty_recipient: owner.clone(),
+        attributes: heavy_attributes(&env),
+        edition_number: None,
+        total_editions: None,
+    };
+    let legacy = LegacyLayoutClient::new(&env, &env.register(LegacyLayout, ()));
+    let split = SplitLayoutClient::new(&env, &env.register(SplitLayout, ()));
+
+    legacy.mint(&data);
+    let legacy_mint = env.cost_estimate().resources();
+    split.mint(&data);
+    let split_mint = env.cost_estimate().resources();
+
+    // Metadata no longer carries the id and a copy of the owner
+    assert_eq!(split_mint.write_entries, legacy_mint.write_entries);
+    assert!(split_mint.write_bytes < legacy_mint.write_bytes);
+
+    legacy.approve(&1, &operator);
+    split.approve(&1, &operator);
+
+    // Let the entries lapse so the transfer pays to read them back in
+    env.ledger().with_mut(|l| l.sequence_number += 5_000_000);
+    legacy.transfer(&1, &recipient);
+    let legacy_transfer = env.cost_estimate().resources();
+    split.transfer(&1, &recipient);
+    let split_transfer = env.cost_estimate().resources();
+
+    // Owner and approval come from one entry instead of two
+    assert!(split_transfer.disk_read_entries < legacy_transfer.disk_read_entries);
+    assert!(split_transfer.disk_read_bytes < legacy_transfer.disk_read_bytes);
+    assert!(split_transfer.write_entries < legacy_transfer.write_entries);
+    // The rewritten entry holds the empty approval slot, a few bytes more
+    // than the bare owner the legacy layout wrote
+    assert!(split_transfer.write_bytes <= legacy_transfer.write_bytes + 16);
+}
+
+#[test]
+fn test_migrate_legacy_token_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let minter = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let legacy = TokenData {
+        id: 7,
+        owner: minter.clone(), // stale: legacy transfers never updated it
+        metadata_uri: String::from_str(&env, "ipfs://legacy"),
+        created_at: 0,
+        creator: minter.clone(),
+        royalty_percentage: 500,
+        royalty_recipient: admin.clone(),
+        attributes: Vec::new(&env),
+        edition_number: None,
+        total_editions: None,
+    };
+    env.as_contract(&client.address, || {
+        let store = env.storage().persistent();
+        store.set(&DataKey::TokenData(7), &legacy);
+        store.set(&DataKey::TokenOwner(7), &owner);
+        store.set(&DataKey::TokenApproved(7), &operator);
+        store.set(&DataKey::Balance(owner.clone()), &1u64);
+        env.storage().instance().set(&DataKey::TotalSupply, &1u64);
+    });
+
+    let ids = Vec::from_array(&env, [7u64, 8]);
+    assert_eq!(client.migrate_token_storage(&admin, &ids), 1);
+    let migrated_at = env.ledger().sequence();
+    assert_eq!(client.owner_of(&7), owner);
+    assert_eq!(client.get_approved(&7), Some(operator.clone()));
+    assert_eq!(client.token_metadata(&7).owner, owner);
+    assert_eq!(
+        client.token_uri(&7),
+        String::from_str(&env, "ipfs://legacy")
+    );
+
+    // Legacy entries are gone and a second pass is a no-op
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&DataKey::TokenData(7)));
+        assert!(!env.storage().persistent().has(&DataKey::TokenOwner(7)));
+    });
+    assert_eq!(client.migrate_token_storage(&admin, &ids), 0);
+
+    // Holder list and checkpoints pick up the legacy balance exactly once
+    assert_eq!(
+        client.holders(&0, &10),
+        Vec::from_array(&env, [(owner.clone(), 1u64)])
+    );
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert_eq!(client.balance_of_at(&owner, &migrated_at), 1);
+    assert_eq!(client.votes_of_at(&owner, &migrated_at), 1);
+    assert_eq!(client.total_supply_at(&migrated_at), 1);
+
+    client.transfer(&operator, &owner, &minter, &7);
+    assert_eq!(client.owner_of(&7), minter);
+    assert_eq!(
+        client.holders(&0, &10),
+        Vec::from_array(&env, [(minter.clone(), 1u64)])
+    );
+    assert_eq!(client.votes_of(&owner), 0);
+}
+
+#[test]
+fn test_votes_stay_exact_when_legacy_holders_act_before_migration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let minter = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let delegatee = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        let store = env.storage().persistent();
+        for id in [7u64, 8] {
+            let legacy = TokenData {
+                id,
+                owner: owner.clone(),
+                metadata_uri: String::from_str(&env, "ipfs://legacy"),
+                created_at: 0,
+                creator: minter.clone(),
+                royalty_percentage: 500,
+                royalty_recipient: admin.clone(),
+                attributes: Vec::new(&env),
+                edition_number: None,
+                total_editions: None,
+            };
+            store.set(&DataKey::TokenData(id), &legacy);
+            store.set(&DataKey::TokenOwner(id), &owner);
+        }
+        store.set(&DataKey::Balance(owner.clone()), &2u64);
+        env.storage().instance().set(&DataKey::TotalSupply, &2u64);
+        env.storage().instance().set(&DataKey::NextTokenId, &9u64);
+    });
+
+    // Activity on the upgraded code before the migration credits the
+    // legacy balance along with the new token
+    let uri = String::from_str(&env, "ipfs://new");
+    client.mint(&admin, &owner, &uri, &Vec::new(&env), &None);
+    assert_eq!(client.votes_of(&owner), 3);
+    client.delegate(&owner, &delegatee);
+    assert_eq!(client.votes_of(&owner), 0);
+    assert_eq!(client.votes_of(&delegatee), 3);
+
+    let ids = Vec::from_array(&env, [7u64, 8]);
+    assert_eq!(client.migrate_token_storage(&admin, &ids), 2);
+    assert_eq!(client.votes_of(&delegatee), 3);
+
+    client.transfer(&owner, &owner, &minter, &7);
+    assert_eq!(client.votes_of(&delegatee), 2);
+    assert_eq!(client.votes_of(&minter), 1);
+    assert_eq!(client.votes_of(&owner), 0);
+}
+
+// ─── Content hashes & media ──────────────────────────────────────────────────
+
+#[test]
+fn test_mint_with_media_and_verify_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let json = Bytes::from_slice(&env, br#"{"name":"Genesis #1"}"#);
+    let content_hash: BytesN<32> = env.crypto().sha256(&json).into();
+    let media = TokenMedia {
+        content_hash: Some(content_hash.clone()),
+        image_uri: Some(String::from_str(&env, "ipfs://image")),
+        animation_url: None,
+        media_hash: Some(BytesN::from_array(&env, &[7; 32])),
+    };
+    let uri = String::from_str(&env, "ipfs://json");
+    let token_id = client.mint_with_media(&admin, &user, &uri, &Vec::new(&env), &None, &media);
+
+    assert_eq!(client.token_media(&token_id), media);
+    assert!(client.verify_metadata(&token_id, &content_hash));
+    assert!(!client.verify_metadata(&token_id, &BytesN::from_array(&env, &[0; 32])));
+
+    // Tokens minted without media have nothing to verify against
+    let plain = client.mint(&admin, &user, &uri, &Vec::new(&env), &None);
+    assert_eq!(client.token_media(&plain), TokenMedia::default());
+    assert!(!client.verify_metadata(&plain, &content_hash));
+
+    // Empty URIs are rejected
+    let bad = TokenMedia {
+        image_uri: Some(String::from_str(&env, "")),
+        ..TokenMedia::default()
+    };
+    let result = client.try_mint_with_media(&admin, &user, &uri, &Vec::new(&env), &None, &bad);
+    assert_eq!(result, Err(Ok(ContractError::InvalidUri)));
+}
+
+#[test]
+fn test_token_media_frozen_with_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://json");
+    let token_id = client.mint(&admin, &user, &uri, &Vec::new(&env), &None);
+
+    let media = TokenMedia {
+        animation_url: Some(String::from_str(&env, "ipfs://anim")),
+        ..TokenMedia::default()
+    };
+    // Only the owner may set media URIs
+    let result = client.try_set_token_media(&admin, &token_id, &media);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.set_token_media(&user, &token_id, &media);
+    assert_eq!(client.token_media(&token_id), media);
+
+    // The creator may record a hash
+    let hashed = TokenMedia {
+        content_hash: Some(BytesN::from_array(&env, &[1; 32])),
+        ..media.clone()
+    };
+    client.set_token_media(&admin, &token_id, &hashed);
+    assert_eq!(client.token_media(&token_id), hashed);
+
+    client.freeze_metadata(&admin);
+    let result = client.try_set_token_media(&user, &token_id, &media);
+    assert_eq!(result, Err(Ok(ContractError::MetadataFrozen)));
+    assert_eq!(client.token_media(&token_id), hashed);
+}
+
+#[test]
+fn test_later_owner_cannot_change_content_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let minted_to = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let updater = Address::generate(&env);
+    let original: BytesN<32> = BytesN::from_array(&env, &[1; 32]);
+    let media = TokenMedia {
+        content_hash: Some(original.clone()),
+        media_hash: Some(BytesN::from_array(&env, &[2; 32])),
+        ..TokenMedia::default()
+    };
+    let uri = String::from_str(&env, "ipfs://json");
+    let token_id = client.mint_with_media(&admin, &minted_to, &uri, &Vec::new(&env), &None, &media);
+    client.transfer(&minted_to, &minted_to, &buyer, &token_id);
+
+    // The new owner can neither swap nor clear either hash
+    let forged = TokenMedia {
+        content_hash: Some(BytesN::from_array(&env, &[9; 32])),
+        ..media.clone()
+    };
+    let result = client.try_set_token_media(&buyer, &token_id, &forged);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    let cleared = TokenMedia {
+        media_hash: None,
+        ..media.clone()
+    };
+    let result = client.try_set_token_media(&buyer, &token_id, &cleared);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    assert!(client.verify_metadata(&token_id, &original));
+
+    // URIs stay with the owner
+    let with_image = TokenMedia {
+        image_uri: Some(String::from_str(&env, "ipfs://image")),
+        ..media.clone()
+    };
+    client.set_token_media(&buyer, &token_id, &with_image);
+    assert_eq!(client.token_media(&token_id), with_image);
+
+    // A metadata updater may correct the hash, and the change is announced
+    client.grant_role(&admin, &updater, &role::METADATA_UPDATER);
+    let corrected = TokenMedia {
+        content_hash: Some(BytesN::from_array(&env, &[3; 32])),
+        ..with_image.clone()
+    };
+    client.set_token_media(&updater, &token_id, &corrected);
+    assert!(env.events().all().iter().any(|e| e.1.iter().any(|t| {
+        if let Ok(sym) = Symbol::try_from_val(&env, &t) {
+            sym == Symbol::new(&env, "token_media_update")
+        } else {
+            false
+        }
+    })));
+    assert!(!client.verify_metadata(&token_id, &original));
+}
+
+// ─── Guardian recovery ───────────────────────────────────────────────────────
+
+#[test]
+fn test_guardian_recovery_after_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let g1 = Address::generate(&env);
+    let g2 = Address::generate(&env);
+    let g3 = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let t1 = client.mint(&admin, &owner, &uri, &Vec::new(&env), &None);
+    let t2 = client.mint(&admin, &owner, &uri, &Vec::new(&env), &None);
+
+    let guardians = Vec::from_array(&env, [g1.clone(), g2.clone(), g3.clone()]);
+    client.set_guardians(&owner, &guardians, &2, &86_400);
+
+    env.ledger().set_timestamp(1_000);
+    client.initiate_recovery(&g1, &owner, &new_owner);
+    let ids = Vec::from_array(&env, [t1, t2]);
+    // Below threshold: the delay has not started
+    let result = client.try_execute_recovery(&owner, &ids);
+    assert_eq!(result, Err(Ok(ContractError::RecoveryNotReady)));
+
+    client.approve_recovery(&g2, &owner);
+    assert_eq!(client.recovery_request(&owner).unwrap().ready_at, 87_400);
+    let result = client.try_execute_recovery(&owner, &ids);
+    assert_eq!(result, Err(Ok(ContractError::RecoveryNotReady)));
+
+    env.ledger().set_timestamp(87_400);
+    assert_eq!(client.execute_recovery(&owner, &ids), 2);
+    assert_eq!(client.owner_of(&t1), new_owner);
+    assert_eq!(client.owner_of(&t2), new_owner);
+    assert_eq!(client.balance_of(&owner), 0);
+    assert!(client.recovery_request(&owner).is_none());
+}
+
+#[test]
+fn test_owner_cancels_recovery_during_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let owner = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let g1 = Address::generate(&env);
+    let g2 = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let token_id = client.mint(&admin, &owner, &uri, &Vec::new(&env), &None);
+
+    // Threshold above the guardian count and a too-short delay are rejected
+    let guardians = Vec::from_array(&env, [g1.clone(), g2.clone()]);
+    let result = client.try_set_guardians(&owner, &guardians, &3, &86_400);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    let result = client.try_set_guardians(&owner, &guardians, &1, &60);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    client.set_guardians(&owner, &guardians, &1, &86_400);
+
+    // Only guardians can start or approve, and only once each
+    let result = client.try_initiate_recovery(&attacker, &owner, &attacker);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+    client.initiate_recovery(&g1, &owner, &attacker);
+    let result = client.try_approve_recovery(&g1, &owner);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyApproved)));
+
+    client.cancel_recovery(&owner);
+    assert!(client.recovery_request(&owner).is_none());
+    env.ledger().set_timestamp(86_400);
+    let result = client.try_execute_recovery(&owner, &Vec::from_array(&env, [token_id]));
+    assert_eq!(result, Err(Ok(ContractError::NotFound)));
+    assert_eq!(client.owner_of(&token_id), owner);
+}
+
+// ─── Multi-signature admin actions ───────────────────────────────────────────
+
+fn setup_multisig<'a>(
+    env: &'a Env,
+    protected: &[u32],
+) -> (NftContractClient<'a>, Address, Address, Address) {
+    let admin = Address::generate(env);
+    let co1 = Address::generate(env);
+    let co2 = Address::generate(env);
+    let contract_id = env.register(NftContract, ());
+    let client = NftContractClient::new(env, &contract_id);
+    let mut protected_actions = Vec::from_array(
+        env,
+        [
+            admin_action::GRANT_ROLE,
+            admin_action::REVOKE_ROLE,
+            admin_action::UPGRADE,
+        ],
+    );
+    protected_actions.extend_from_slice(protected);
+    let multisig = MultisigConfig {
+        co_admins: Vec::from_array(env, [co1.clone(), co2.clone()]),
+        threshold: 2,
+        protected_actions,
+        proposal_ttl: 3_600,
+    };
+    client.initialize(&admin, &make_config(env), &None, &Some(multisig));
+    (client, admin, co1, co2)
+}
+
+#[test]
+fn test_protected_pause_requires_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, co1, _co2) = setup_multisig(&env, &[admin_action::SET_PAUSE]);
+
+    let result = client.try_set_pause(&admin, &true);
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired.into())));
+
+    let id = client.propose_admin_action(&admin, &AdminCall::SetPause(true));
+    let result = client.try_execute_admin_action(&admin, &id);
+    assert_eq!(result, Err(Ok(ContractError::ThresholdNotMet)));
+    let result = client.try_approve_admin_action(&admin, &id);
+    assert_eq!(result, Err(Ok(ContractError::AlreadyApproved)));
+
+    client.approve_admin_action(&co1, &id);
+    client.execute_admin_action(&co1, &id);
+    assert!(client.is_paused());
+    assert!(client.admin_proposal(&id).is_none());
+
+    // Outsiders cannot propose
+    let outsider = Address::generate(&env);
+    let result = client.try_propose_admin_action(&outsider, &AdminCall::SetPause(false));
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
+#[test]
+fn test_admin_proposal_expiry_and_revoked_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, co1, co2) = setup_multisig(
+        &env,
+        &[
+            admin_action::SET_DEFAULT_ROYALTY,
+            admin_action::SET_BASE_URI,
+        ],
+    );
+
+    // Unprotected actions still work with a single key
+    client.set_pause(&admin, &true);
+    let result = client.try_set_base_uri(&admin, &String::from_str(&env, "ipfs://new/"));
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired)));
+
+    let call = AdminCall::SetDefaultRoyalty(co2.clone(), 1_000);
+    let expiring = client.propose_admin_action(&admin, &call);
+    env.ledger().set_timestamp(3_601);
+    let result = client.try_approve_admin_action(&co1, &expiring);
+    assert_eq!(result, Err(Ok(ContractError::ProposalExpired)));
+
+    // An approval stops counting once the approver loses ADMIN
+    let id = client.propose_admin_action(&co1, &call);
+    client.approve_admin_action(&co2, &id);
+    let revoke =
+        client.propose_admin_action(&admin, &AdminCall::RevokeRole(co2.clone(), role::ADMIN));
+    client.approve_admin_action(&co1, &revoke);
+    client.execute_admin_action(&co1, &revoke);
+    let result = client.try_execute_admin_action(&co1, &id);
+    assert_eq!(result, Err(Ok(ContractError::ThresholdNotMet)));
+
+    client.approve_admin_action(&admin, &id);
+    client.execute_admin_action(&admin, &id);
+    assert_eq!(client.collection_info().royalty_percentage, 1_000);
+    assert_eq!(client.collection_info().royalty_recipient, Some(co2));
+}
+
+#[test]
+fn test_single_owner_key_cannot_bypass_multisig() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, co1, _co2) = setup_multisig(&env, &[admin_action::SET_PAUSE]);
+
+    // Role changes and upgrades are protected in every multisig config
+    let sock = Address::generate(&env);
+    let result = client.try_grant_role(&admin, &sock, &role::ADMIN);
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired.into())));
+    let result = client.try_revoke_role(&admin, &co1, &role::ADMIN);
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired.into())));
+    let result = client.try_upgrade(&admin, &BytesN::from_array(&env, &[0; 32]));
+    assert_eq!(result, Err(Ok(ContractError::MultisigRequired.into())));
+    assert!(!client.has_role(&sock, &role::ADMIN));
+
+    // The owner key alone cannot push a protected call through
+    let grant =
+        client.propose_admin_action(&admin, &AdminCall::GrantRole(sock.clone(), role::ADMIN));
+    let result = client.try_execute_admin_action(&admin, &grant);
+    assert_eq!(result, Err(Ok(ContractError::ThresholdNotMet)));
+    let pause = client.propose_admin_action(&admin, &AdminCall::SetPause(true));
+    let result = client.try_execute_admin_action(&admin, &pause);
+    assert_eq!(result, Err(Ok(ContractError::ThresholdNotMet)));
+    assert!(!client.is_paused());
+
+    // A second signer completes the grant
+    client.approve_admin_action(&co1, &grant);
+    client.execute_admin_action(&admin, &grant);
+    assert!(client.has_role(&sock, &role::ADMIN));
+}
+
+#[test]
+fn test_multisig_config_must_protect_roles_and_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let co_admin = Address::generate(&env);
+    let client = NftContractClient::new(&env, &env.register(NftContract, ()));
+
+    let mut multisig = MultisigConfig {
+        co_admins: Vec::from_array(&env, [co_admin]),
+        threshold: 2,
+        protected_actions: Vec::from_array(
+            &env,
+            [admin_action::SET_PAUSE, admin_action::GRANT_ROLE],
+        ),
+        proposal_ttl: 3_600,
+    };
+    let result = client.try_initialize(&admin, &make_config(&env), &None, &Some(multisig.clone()));
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+
+    multisig
+        .protected_actions
+        .push_back(admin_action::REVOKE_ROLE);
+    multisig.protected_actions.push_back(admin_action::UPGRADE);
+    client.initialize(&admin, &make_config(&env), &None, &Some(multisig));
+    let stored = client.multisig_config().unwrap();
+    assert!(stored.protected_actions.contains(admin_action::UPGRADE));
+    assert!(
+        !stored
+            .protected_actions
+            .contains(admin_action::SET_BASE_URI)
+    );
+}
+
+// ─── Burn-to-redeem ──────────────────────────────────────────────────────────
+
+fn attr(env: &Env, trait_type: &str, value: &str) -> Vec<TokenAttribute> {
+    Vec::from_array(
+        env,
+        [TokenAttribute {
+            trait_type: String::from_str(env, trait_type),
+            value: String::from_str(env, value),
+            display_type: None,
+        }],
+    )
+}
+
+#[test]
+fn test_redeem_by_attribute_mints_output() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let shard1 = client.mint(&admin, &user, &uri, &attr(&env, "Kind", "Shard"), &None);
+    let shard2 = client.mint(&admin, &user, &uri, &attr(&env, "Kind", "Shard"), &None);
+    let other = client.mint(&admin, &user, &uri, &attr(&env, "Kind", "Gem"), &None);
+
+    let inputs = Vec::from_array(
+        &env,
+        [RecipeInput::Attribute(
+            String::from_str(&env, "Kind"),
+            String::from_str(&env, "Shard"),
+            2,
+        )],
+    );
+    let output = RecipeOutput::Mint(
+        String::from_str(&env, "ipfs://sword"),
+        attr(&env, "Kind", "Sword"),
+    );
+    let recipe_id = client.create_recipe(&admin, &inputs, &output);
+
+    let result = client.try_redeem(&user, &Vec::from_array(&env, [shard1, other]), &recipe_id);
+    assert_eq!(result, Err(Ok(ContractError::RecipeMismatch)));
+    let result = client.try_redeem(&user, &Vec::from_array(&env, [shard1]), &recipe_id);
+    assert_eq!(result, Err(Ok(ContractError::RecipeMismatch)));
+
+    let minted = client
+        .redeem(&user, &Vec::from_array(&env, [shard1, shard2]), &recipe_id)
+        .unwrap();
+    assert_eq!(client.owner_of(&minted), user);
+    assert_eq!(
+        client.token_uri(&minted),
+        String::from_str(&env, "ipfs://sword")
+    );
+    assert!(client.try_owner_of(&shard1).is_err());
+    assert!(client.try_owner_of(&shard2).is_err());
+    assert_eq!(client.balance_of(&user), 2);
+    assert_eq!(client.recipe(&recipe_id).unwrap().redeemed_count, 1);
+
+    client.set_recipe_active(&admin, &recipe_id, &false);
+    let result = client.try_redeem(&user, &Vec::from_array(&env, [other]), &recipe_id);
+    assert_eq!(result, Err(Ok(ContractError::RecipeInactive)));
+}
+
+#[test]
+fn test_redeem_into_other_collection_is_atomic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let (rewards, rewards_admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let ticket = client.mint(&admin, &user, &uri, &Vec::new(&env), &None);
+    let filler = client.mint(&admin, &user, &uri, &Vec::new(&env), &None);
+
+    let inputs = Vec::from_array(&env, [RecipeInput::Token(ticket), RecipeInput::Any(1)]);
+    let output = RecipeOutput::MintExternal(
+        rewards.address.clone(),
+        String::from_str(&env, "ipfs://reward"),
+        Vec::new(&env),
+    );
+    let recipe_id = client.create_recipe(&admin, &inputs, &output);
+    let ids = Vec::from_array(&env, [ticket, filler]);
+
+    // Without MINTER on the rewards collection the mint fails and nothing is burned
+    assert!(client.try_redeem(&user, &ids, &recipe_id).is_err());
+    assert_eq!(client.owner_of(&ticket), user);
+    assert_eq!(client.balance_of(&user), 2);
+
+    rewards.grant_role(&rewards_admin, &client.address, &role::MINTER);
+    let reward_id = client.redeem(&user, &ids, &recipe_id).unwrap();
+    assert_eq!(rewards.owner_of(&reward_id), user);
+    assert_eq!(client.balance_of(&user), 0);
+
+    // Receipt-only recipes mint nothing; inputs must belong to the redeemer
+    let receipt = client.create_recipe(
+        &admin,
+        &Vec::from_array(&env, [RecipeInput::Any(1)]),
+        &RecipeOutput::Receipt,
+    );
+    let stranger_token = client.mint(&admin, &admin, &uri, &Vec::new(&env), &None);
+    let result = client.try_redeem(&user, &Vec::from_array(&env, [stranger_token]), &receipt);
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    assert_eq!(
+        client.redeem(&admin, &Vec::from_array(&env, [stranger_token]), &receipt),
+        None
+    );
+}
+
+// ─── Holder snapshots ────────────────────────────────────────────────────────
+
+#[test]
+fn test_holders_paginates_and_drops_exited_holders() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let t1 = client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    let t3 = client.mint(&admin, &b, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &c, &uri, &Vec::new(&env), &None);
+
+    let page = client.holders(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap(), (a.clone(), 2));
+    assert_eq!(page.get(1).unwrap(), (b.clone(), 1));
+    assert_eq!(client.holders(&2, &10).len(), 1);
+
+    // b exits; the last holder takes its slot
+    client.transfer(&b, &b, &a, &t3);
+    client.transfer(&a, &a, &c, &t1);
+    let all = client.holders(&0, &10);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all.get(0).unwrap(), (a.clone(), 2));
+    assert_eq!(all.get(1).unwrap(), (c.clone(), 2));
+    assert_eq!(client.collection_info().holder_count, 2);
+}
+
+#[test]
+fn test_owners_of_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    let burned = client.mint(&admin, &b, &uri, &Vec::new(&env), &None);
+    client.mint(&admin, &b, &uri, &Vec::new(&env), &None);
+    client.burn(&b, &burned);
+
+    let owners = client.owners_of_range(&1, &4);
+    assert_eq!(
+        owners,
+        Vec::from_array(&env, [Some(a.clone()), None, Some(b.clone()), None])
+    );
+
+    let result = client.try_owners_of_range(&1, &100);
+    assert!(result.is_ok());
+    let result = client.try_owners_of_range(&1, &101);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBatchSize)));
+    let result = client.try_owners_of_range(&5, &4);
+    assert_eq!(result, Err(Ok(ContractError::InvalidBatchSize)));
+}
+
+// ─── Provenance ──────────────────────────────────────────────────────────────
+
+#[test]
+fn test_provenance_records_mint_transfer_burn() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+
+    env.ledger().set_timestamp(100);
+    let token_id = client.mint(&admin, &alice, &uri, &Vec::new(&env), &None);
+    client.set_approval_for_all(&alice, &operator, &true);
+    env.ledger().set_timestamp(200);
+    client.transfer(&operator, &alice, &bob, &token_id);
+    env.ledger().set_timestamp(300);
+    client.burn(&bob, &token_id);
+
+    // The log outlives the token
+    let log = client.provenance(&token_id, &0, &10);
+    assert_eq!(log.len(), 3);
+    let mint = log.get(0).unwrap();
+    assert_eq!(mint.action, ProvenanceAction::Mint);
+    assert_eq!((mint.from, mint.to), (None, Some(alice.clone())));
+    assert_eq!((mint.operator, mint.timestamp), (admin.clone(), 100));
+    let moved = log.get(1).unwrap();
+    assert_eq!(moved.action, ProvenanceAction::Transfer);
+    assert_eq!((moved.from, moved.to), (Some(alice), Some(bob.clone())));
+    assert_eq!((moved.operator, moved.timestamp), (operator, 200));
+    let burned = log.get(2).unwrap();
+    assert_eq!(burned.action, ProvenanceAction::Burn);
+    assert_eq!((burned.from, burned.to), (Some(bob.clone()), None));
+    assert_eq!(burned.operator, bob);
+}
+
+#[test]
+fn test_provenance_cap_rolls_off_old_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    client.set_provenance_cap(&admin, &3);
+    let token_id = client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    for i in 0..4u64 {
+        env.ledger().set_timestamp(i + 1);
+        let (from, to) = if i % 2 == 0 { (&a, &b) } else { (&b, &a) };
+        client.transfer(from, from, to, &token_id);
+    }
+
+    // Mint plus four transfers, capped at the three newest
+    let log = client.provenance(&token_id, &0, &10);
+    assert_eq!(log.len(), 3);
+    assert_eq!(log.get(0).unwrap().timestamp, 2);
+    assert_eq!(log.get(2).unwrap().timestamp, 4);
+    let page = client.provenance(&token_id, &2, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().timestamp, 4);
+
+    let result = client.try_set_provenance_cap(&admin, &101);
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+    env.as_contract(&client.address, || {
+        assert!(
+            !env.storage()
+                .persistent()
+                .has(&DataKey::Provenance(token_id, 1))
+        );
+        assert!(
+            env.storage()
+                .persistent()
+                .has(&DataKey::Provenance(token_id, 2))
+        );
+    });
+    client.set_provenance_cap(&admin, &0);
+    let untracked = client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    assert_eq!(client.provenance(&untracked, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_lowering_provenance_cap_deletes_excess_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let busy = client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    let idle = client.mint(&admin, &a, &uri, &Vec::new(&env), &None);
+    for i in 0..15u64 {
+        let (from, to) = if i % 2 == 0 { (&a, &b) } else { (&b, &a) };
+        client.transfer(from, from, to, &busy);
+    }
+    client.transfer(&a, &a, &b, &idle);
+    let stored = |token_id: u64, seq: u32| {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .has(&DataKey::Provenance(token_id, seq))
+        })
+    };
+
+    // Sixteen entries on `busy`; the lower cap hides all but the newest two
+    client.set_provenance_cap(&admin, &2);
+    assert_eq!(client.provenance(&busy, &0, &20).len(), 2);
+    assert!(stored(busy, 0));
+
+    // The next write deletes a bounded slice of the excess
+    client.transfer(&b, &b, &a, &busy);
+    assert!(!stored(busy, 9));
+    assert!(stored(busy, 10));
+    assert_eq!(client.trim_provenance(&busy), 5);
+    assert_eq!(client.trim_provenance(&busy), 0);
+    assert!(!stored(busy, 14));
+    assert!(stored(busy, 15) && stored(busy, 16));
+    assert_eq!(client.provenance(&busy, &0, &20).len(), 2);
+
+    // Disabling the log clears a token outright
+    client.set_provenance_cap(&admin, &0);
+    assert_eq!(client.trim_provenance(&idle), 2);
+    assert!(!stored(idle, 0) && !stored(idle, 1));
+}
+
+// ─── Multi-recipient batches ─────────────────────────────────────────────────
+
+#[test]
+fn test_batch_transfer_many_and_batch_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let treasury = Address::generate(&env);
+    let r1 = Address::generate(&env);
+    let r2 = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://hash");
+    let t1 = client.mint(&admin, &treasury, &uri, &Vec::new(&env), &None);
+    let t2 = client.mint(&admin, &treasury, &uri, &Vec::new(&env), &None);
+    let t3 = client.mint(&admin, &treasury, &uri, &Vec::new(&env), &None);
+    let foreign = client.mint(&admin, &r1, &uri, &Vec::new(&env), &None);
+
+    // One foreign token fails the whole approval batch
+    let result =
+        client.try_batch_approve(&treasury, &operator, &Vec::from_array(&env, [t1, foreign]));
+    assert_eq!(result, Err(Ok(ContractError::NotOwner)));
+    assert_eq!(client.get_approved(&t1), None);
+
+    client.batch_approve(&treasury, &operator, &Vec::from_array(&env, [t1, t2, t3]));
+    assert_eq!(client.get_approved(&t2), Some(operator.clone()));
+
+    let transfers = Vec::from_array(&env, [(r1.clone(), t1), (r2.clone(), t2), (r2.clone(), t3)]);
+    client.batch_transfer_many(&operator, &treasury, &transfers);
+    let (_, _, data) = env.events().all().last().unwrap();
+    let data = Map::<Symbol, Val>::try_from_val(&env, &data).unwrap();
+    let to = Vec::<Address>::try_from_val(&env, &data.get(Symbol::new(&env, "to")).unwrap());
+    assert_eq!(
+        to.unwrap(),
+        Vec::from_array(&env, [r1.clone(), r2.clone(), r2.clone()])
+    );
+    assert_eq!(client.owner_of(&t1), r1);
+    assert_eq!(client.owner_of(&t2), r2);
+    assert_eq!(client.balance_of(&r2), 2);
+    assert_eq!(client.balance_of(&treasury), 0);
+
+    // Atomic: the unapproved second entry reverts the first
+    let transfers = Vec::from_array(&env, [(treasury.clone(), t2), (treasury.clone(), foreign)]);
+    let result = client.try_batch_transfer_many(&r2, &r2, &transfers);
+    assert_eq!(result, Err(Ok(ContractError::NotApproved)));
+    assert_eq!(client.owner_of(&t2), r2);
+}
+
+#[test]
+fn test_batch_set_token_uri() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let user = Address::generate(&env);
+    let uri = String::from_str(&env, "ipfs://old");
+    let t1 = client.mint(&admin, &user, &uri, &Vec::new(&env), &None);
+    let t2 = client.mint(&admin, &user, &uri, &Vec::new(&env), &None);
+    let ids = Vec::from_array(&env, [t1, t2]);
+
+    let result = client.try_batch_set_token_uri(&user, &ids, &Vec::from_array(&env, [uri.clone()]));
+    assert_eq!(result, Err(Ok(ContractError::MismatchedArrays)));
+
+    let uris = Vec::from_array(
+        &env,
+        [
+            String::from_str(&env, "ipfs://new1"),
+            String::from_str(&env, "ipfs://new2"),
+        ],
+    );
+    client.batch_set_token_uri(&user, &ids, &uris);
+    assert_eq!(client.token_uri(&t1), String::from_str(&env, "ipfs://new1"));
+    assert_eq!(client.token_uri(&t2), String::from_str(&env, "ipfs://new2"));
+
+    let mut too_many: Vec<u64> = Vec::new(&env);
+    let mut many_uris: Vec<String> = Vec::new(&env);
+    for _ in 0..51 {
+        too_many.push_back(t1);
+        many_uris.push_back(uri.clone());
+    }
+    let result = client.try_batch_set_token_uri(&user, &too_many, &many_uris);
+    assert_eq!(result, Err(Ok(ContractError::BatchTooLarge)));
+}
@@ -1,21 +1,44 @@
 use crate::access_control;
+use crate::checkpoints;
 use crate::error::ContractError;
 use crate::events;
+use crate::holders;
+use crate::lock;
+use crate::metadata;
+use crate::provenance;
+use crate::reservation;
 use crate::storage::{DataKey, MAX_BATCH_SIZE, MAX_SUPPLY_HARD_CAP};
 use crate::transfer;
-use crate::types::{CollectionConfig, RoyaltyInfo, TokenAttribute, TokenData};
+use crate::transfer_rules;
+use crate::types::{
+    CollectionConfig, ProvenanceAction, RoyaltyInfo, TokenAttribute, TokenMedia, TokenMetadata,
+    TokenOwnership,
+};
 use soroban_sdk::{Address, Env, String, Vec};
 
-fn next_token_id(env: &Env) -> u64 {
-    let id: u64 = env
-        .storage()
-        .instance()
-        .get(&DataKey::NextTokenId)
-        .unwrap_or(1u64);
+fn next_id_cursor(env: &Env) -> u64 {
     env.storage()
         .instance()
-        .set(&DataKey::NextTokenId, &(id + 1));
-    id
+        .get(&DataKey::NextTokenId)
+        .unwrap_or(1u64)
+}
+
+/// Allocates the next free sequential ID, jumping over reserved ranges one
+/// range per step. `mint_with_id` moves the cursor past any unreserved ID it
+/// takes at or beyond it, so no taken ID ever lies ahead outside a range.
+fn next_token_id(env: &Env) -> Result<u64, ContractError> {
+    let ranges = reservation::get_ranges(env);
+    let mut id = next_id_cursor(env);
+    while let Some(range) = reservation::range_containing(&ranges, id) {
+        id = range
+            .end
+            .checked_add(1)
+            .ok_or(ContractError::ArithmeticError)?;
+    }
+    let next = id.checked_add(1).ok_or(ContractError::ArithmeticError)?;
+    env.storage().instance().set(&DataKey::NextTokenId, &next);
+    reservation::prune_passed(env, ranges, id);
+    Ok(id)
 }
 
 fn check_supply(env: &Env) -> Result<(), ContractError> {
@@ -39,17 +62,40 @@ fn check_supply(env: &Env) -> Result<(), ContractError> {
     Ok(())
 }
 
-fn mint_one(
+pub fn mint_one(
+    env: &Env,
+    caller: &Address,
+    to: &Address,
+    metadata_uri: String,
+    attributes: Vec<TokenAttribute>,
+    royalty_override: Option<RoyaltyInfo>,
+) -> Result<u64, ContractError> {
+    let token_id = next_token_id(env)?;
+    mint_token(
+        env,
+        caller,
+        to,
+        token_id,
+        metadata_uri,
+        attributes,
+        royalty_override,
+    )
+}
+
+/// Writes a new token under `token_id`, which the caller has already checked is free.
+fn mint_token(
     env: &Env,
     caller: &Address,
     to: &Address,
+    token_id: u64,
     metadata_uri: String,
     attributes: Vec<TokenAttribute>,
     royalty_override: Option<RoyaltyInfo>,
 ) -> Result<u64, ContractError> {
     check_supply(env)?;
+    let rules = transfer_rules::get_rules(env);
+    transfer_rules::check_transfer(env, &rules, None, to, token_id)?;
 
-    let token_id = next_token_id(env);
     let default_royalty: RoyaltyInfo = env
         .storage()
         .instance()
@@ -61,9 +107,7 @@ fn mint_one(
 
     let royalty = royalty_override.unwrap_or(default_royalty);
 
-    let data = TokenData {
-        id: token_id,
-        owner: to.clone(),
+    let meta = TokenMetadata {
         metadata_uri,
         created_at: env.ledger().timestamp(),
         creator: caller.clone(),
@@ -73,22 +117,11 @@ fn mint_one(
         edition_number: None,
         total_editions: None,
     };
+    save_metadata(env, token_id, &meta);
+    save_ownership(env, token_id, &TokenOwnership(to.clone(), None));
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::TokenData(token_id), &data);
-    env.storage()
-        .persistent()
-        .set(&DataKey::TokenOwner(token_id), to);
-
-    let bal: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Balance(to.clone()))
-        .unwrap_or(0);
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(to.clone()), &(bal + 1));
+    increment_balance(env, to);
+    transfer_rules::record_receipt(env, &rules, token_id);
 
     let total: u64 = env
         .storage()
@@ -98,6 +131,15 @@ fn mint_one(
     env.storage()
         .instance()
         .set(&DataKey::TotalSupply, &(total + 1));
+    checkpoints::on_supply_change(env, total + 1);
+    provenance::record(
+        env,
+        token_id,
+        ProvenanceAction::Mint,
+        None,
+        Some(to.clone()),
+        caller,
+    );
 
     events::emit_mint(env, to.clone(), token_id);
     Ok(token_id)
@@ -125,6 +167,69 @@ pub fn mint(
     mint_one(env, caller, &to, metadata_uri, attributes, royalty_override)
 }
 
+/// Mints a token carrying a content hash and/or media fields.
+pub fn mint_with_media(
+    env: &Env,
+    caller: &Address,
+    to: Address,
+    metadata_uri: String,
+    attributes: Vec<TokenAttribute>,
+    royalty_override: Option<RoyaltyInfo>,
+    media: TokenMedia,
+) -> Result<u64, ContractError> {
+    let token_id = mint(env, caller, to, metadata_uri, attributes, royalty_override)?;
+    metadata::save_media(env, token_id, &media)?;
+    Ok(token_id)
+}
+
+/// Mints `token_id` explicitly. IDs inside a reserved range may only be
+/// minted by that range's named minters; other IDs need the MINTER role.
+pub fn mint_with_id(
+    env: &Env,
+    caller: &Address,
+    to: Address,
+    token_id: u64,
+    metadata_uri: String,
+    attributes: Vec<TokenAttribute>,
+) -> Result<u64, ContractError> {
+    if token_id == 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    let range = reservation::range_containing(&reservation::get_ranges(env), token_id);
+    match &range {
+        // Released ranges have no named minters and are open to any minter
+        Some(range) if !range.minters.is_empty() => {
+            if !range.minters.contains(caller) {
+                return Err(ContractError::NotAuthorized);
+            }
+        }
+        _ => access_control::require_minter(env, caller)?,
+    }
+
+    if env
+        .storage()
+        .instance()
+        .get::<_, bool>(&DataKey::IsPaused)
+        .unwrap_or(false)
+    {
+        return Err(ContractError::ContractPaused);
+    }
+
+    if exists(env, token_id) {
+        return Err(ContractError::TokenAlreadyExists);
+    }
+
+    // Keep sequential allocation from ever running into this ID
+    if range.is_none() && token_id >= next_id_cursor(env) {
+        let next = token_id
+            .checked_add(1)
+            .ok_or(ContractError::ArithmeticError)?;
+        env.storage().instance().set(&DataKey::NextTokenId, &next);
+    }
+
+    mint_token(env, caller, &to, token_id, metadata_uri, attributes, None)
+}
+
 pub fn batch_mint(
     env: &Env,
     caller: &Address,
@@ -198,32 +303,28 @@ pub fn batch_mint(
 /// - Authorization checked via access_control
 ///
 /// Cleanup:
-/// - Removes TokenOwner, TokenData, TokenApproved entries
+/// - Removes the Token (ownership + approval) and TokenMeta entries
 /// - Decrements owner balance and total supply
 /// - Emits Burn event on success
 /// - Emits BurnFailed event on failure
 pub fn burn(env: &Env, caller: &Address, token_id: u64) -> Result<(), ContractError> {
     // 1. Validate token exists
-    let owner: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenOwner(token_id))
-        .ok_or_else(|| {
-            events::emit_burn_failed(
-                env,
-                token_id,
-                caller.clone(),
-                ContractError::TokenNotFound as u32,
-            );
-            ContractError::TokenNotFound
-        })?;
-
-    // 2. Validate not already burned - check if token data exists
-    let token_data: Option<TokenData> = env
+    let owner: Address = load_ownership(env, token_id).map(|o| o.0).ok_or_else(|| {
+        events::emit_burn_failed(
+            env,
+            token_id,
+            caller.clone(),
+            ContractError::TokenNotFound as u32,
+        );
+        ContractError::TokenNotFound
+    })?;
+
+    // 2. Validate not already burned - check if token metadata exists
+    if !env
         .storage()
         .persistent()
-        .get(&DataKey::TokenData(token_id));
-    if token_data.is_none() {
+        .has(&DataKey::TokenMeta(token_id))
+    {
         events::emit_burn_failed(
             env,
             token_id,
@@ -246,40 +347,41 @@ pub fn burn(env: &Env, caller: &Address, token_id: u64) -> Result<(), ContractEr
         );
         return Err(ContractError::NotAuthorized);
     }
+    if lock::is_locked(env, token_id) {
+        events::emit_burn_failed(
+            env,
+            token_id,
+            caller.clone(),
+            ContractError::TokenLocked as u32,
+        );
+        return Err(ContractError::TokenLocked);
+    }
 
     // 4. Clean up operator approvals for this token
-    // Remove TokenApproved entry
-    env.storage()
-        .persistent()
-        .remove(&DataKey::TokenApproved(token_id));
-
-    // Remove any approval-for-all entries that reference this token
-    // Note: We need to iterate through all operators or use a different approach
-    // For now, we remove the token-specific approval. Approval-for-all is per-owner,
-    // not per-token, so it doesn't need to be cleaned up for individual burns.
+    // The per-token approval lives in the Token entry removed below.
+    // Approval-for-all is per-owner, not per-token, so it doesn't need to be
+    // cleaned up for individual burns.
 
     // 5. Remove token data and ownership
+    env.storage().persistent().remove(&DataKey::Token(token_id));
     env.storage()
         .persistent()
-        .remove(&DataKey::TokenOwner(token_id));
+        .remove(&DataKey::TokenMeta(token_id));
     env.storage()
         .persistent()
-        .remove(&DataKey::TokenData(token_id));
+        .remove(&DataKey::TokenRoyalty(token_id));
     env.storage()
         .persistent()
-        .remove(&DataKey::TokenRoyalty(token_id));
+        .remove(&DataKey::PendingClaim(token_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HeldSince(token_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TokenMedia(token_id));
 
     // 6. Update owner balance
-    let bal: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Balance(owner.clone()))
-        .unwrap_or(0);
-    if bal > 0 {
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(owner.clone()), &bal.saturating_sub(1));
-    }
+    decrement_balance(env, &owner);
 
     // 7. Update total supply
     let total: u64 = env
@@ -291,7 +393,25 @@ pub fn burn(env: &Env, caller: &Address, token_id: u64) -> Result<(), ContractEr
         env.storage()
             .instance()
             .set(&DataKey::TotalSupply, &total.saturating_sub(1));
+        checkpoints::on_supply_change(env, total - 1);
     }
+    let burned: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::BurnedCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::BurnedCount, &(burned + 1));
+
+    provenance::record(
+        env,
+        token_id,
+        ProvenanceAction::Burn,
+        Some(owner.clone()),
+        None,
+        caller,
+    );
 
     // 8. Emit success event
     events::emit_burn(env, owner, token_id);
@@ -326,11 +446,7 @@ pub fn batch_burn(env: &Env, caller: &Address, token_ids: Vec<u64>) -> Result<()
     if !has_burner_role {
         for i in 0..n {
             let token_id = token_ids.get(i).unwrap();
-            let owner: Address = env
-                .storage()
-                .persistent()
-                .get(&DataKey::TokenOwner(token_id))
-                .ok_or(ContractError::TokenNotFound)?;
+            let owner = owner_of(env, token_id)?;
 
             if caller != &owner {
                 return Err(ContractError::NotAuthorized);
@@ -356,10 +472,8 @@ pub fn transfer(
     to: Address,
     token_id: u64,
 ) -> Result<(), ContractError> {
-    if !transfer::is_approved_or_owner(env, caller, token_id) {
-        return Err(ContractError::NotApproved);
-    }
-    transfer::do_transfer(env, &from, &to, token_id)
+    transfer::authorize_transfer(env, caller, token_id)?;
+    transfer::do_transfer(env, caller, &from, &to, token_id)
 }
 
 pub fn safe_transfer_from(
@@ -369,10 +483,8 @@ pub fn safe_transfer_from(
     to: Address,
     token_id: u64,
 ) -> Result<(), ContractError> {
-    if !transfer::is_approved_or_owner(env, caller, token_id) {
-        return Err(ContractError::NotApproved);
-    }
-    transfer::do_transfer(env, &from, &to, token_id)
+    transfer::authorize_transfer(env, caller, token_id)?;
+    transfer::do_transfer(env, caller, &from, &to, token_id)
 }
 
 pub fn batch_transfer(
@@ -389,18 +501,65 @@ pub fn batch_transfer(
 
     for i in 0..n {
         let token_id = token_ids.get(i).unwrap();
-        if !transfer::is_approved_or_owner(env, caller, token_id) {
-            return Err(ContractError::NotApproved);
-        }
-        transfer::do_transfer(env, &from, &to, token_id)?;
+        transfer::authorize_transfer(env, caller, token_id)?;
+        transfer::do_transfer(env, caller, &from, &to, token_id)?;
     }
     Ok(())
 }
 
-pub fn owner_of(env: &Env, token_id: u64) -> Result<Address, ContractError> {
+/// Moves tokens out of `from` to a different recipient each; all-or-nothing.
+pub fn batch_transfer_many(
+    env: &Env,
+    caller: &Address,
+    from: Address,
+    transfers: Vec<(Address, u64)>,
+) -> Result<(), ContractError> {
+    let n = transfers.len();
+    if n == 0 || n > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge);
+    }
+
+    let mut recipients: Vec<Address> = Vec::new(env);
+    let mut token_ids: Vec<u64> = Vec::new(env);
+    for (to, token_id) in transfers.iter() {
+        transfer::authorize_transfer(env, caller, token_id)?;
+        transfer::do_transfer(env, caller, &from, &to, token_id)?;
+        recipients.push_back(to);
+        token_ids.push_back(token_id);
+    }
+    events::emit_batch_transfer(env, caller.clone(), from, recipients, token_ids);
+    Ok(())
+}
+
+pub fn load_ownership(env: &Env, token_id: u64) -> Option<TokenOwnership> {
+    env.storage().persistent().get(&DataKey::Token(token_id))
+}
+
+pub fn save_ownership(env: &Env, token_id: u64, ownership: &TokenOwnership) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Token(token_id), ownership);
+}
+
+pub fn load_metadata(env: &Env, token_id: u64) -> Option<TokenMetadata> {
     env.storage()
         .persistent()
-        .get(&DataKey::TokenOwner(token_id))
+        .get(&DataKey::TokenMeta(token_id))
+}
+
+pub fn save_metadata(env: &Env, token_id: u64, meta: &TokenMetadata) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TokenMeta(token_id), meta);
+}
+
+pub fn exists(env: &Env, token_id: u64) -> bool {
+    env.storage().persistent().has(&DataKey::Token(token_id))
+}
+
+pub fn owner_of(env: &Env, token_id: u64) -> Result<Address, ContractError> {
+    load_ownership(env, token_id)
+        .map(|o| o.0)
         .ok_or(ContractError::TokenNotFound)
 }
 
@@ -411,6 +570,59 @@ pub fn balance_of(env: &Env, owner: &Address) -> u64 {
         .unwrap_or(0)
 }
 
+pub fn burned_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::BurnedCount)
+        .unwrap_or(0)
+}
+
+pub fn holder_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::HolderCount)
+        .unwrap_or(0)
+}
+
+/// Tokens parked in claim escrow sit at the contract's own address. They
+/// count toward its balance but not as holdings or votes.
+fn is_escrow(env: &Env, owner: &Address) -> bool {
+    owner == &env.current_contract_address()
+}
+
+/// Adds one token to `owner`'s balance, counting them as a new holder on 0 → 1.
+pub fn increment_balance(env: &Env, owner: &Address) {
+    let bal = balance_of(env, owner);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &(bal + 1));
+    if is_escrow(env, owner) {
+        return;
+    }
+    checkpoints::sync_balance(env, owner);
+    if bal == 0 {
+        holders::add_holder(env, owner);
+    }
+}
+
+/// Removes one token from `owner`'s balance, dropping them as a holder on 1 → 0.
+pub fn decrement_balance(env: &Env, owner: &Address) {
+    let bal = balance_of(env, owner);
+    if bal == 0 {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(owner.clone()), &(bal - 1));
+    if is_escrow(env, owner) {
+        return;
+    }
+    checkpoints::sync_balance(env, owner);
+    if bal == 1 {
+        holders::remove_holder(env, owner);
+    }
+}
+
 pub fn total_supply(env: &Env) -> u64 {
     env.storage()
         .instance()
@@ -1,7 +1,12 @@
 use crate::error::ContractError;
 use crate::events;
-use crate::storage::DataKey;
-use soroban_sdk::{Address, Env};
+use crate::lock;
+use crate::provenance;
+use crate::storage::{DataKey, MAX_BATCH_SIZE};
+use crate::token;
+use crate::transfer_rules;
+use crate::types::{ProvenanceAction, TokenOwnership};
+use soroban_sdk::{Address, Env, Vec};
 
 pub fn approve(
     env: &Env,
@@ -9,24 +14,40 @@ pub fn approve(
     approved: &Address,
     token_id: u64,
 ) -> Result<(), ContractError> {
-    let token_owner: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenOwner(token_id))
-        .ok_or(ContractError::TokenNotFound)?;
+    let mut ownership = token::load_ownership(env, token_id).ok_or(ContractError::TokenNotFound)?;
 
-    if &token_owner != owner {
+    if ownership.owner() != owner {
         return Err(ContractError::NotOwner);
     }
+    if lock::is_locked(env, token_id) {
+        return Err(ContractError::TokenLocked);
+    }
 
-    env.storage()
-        .persistent()
-        .set(&DataKey::TokenApproved(token_id), approved);
+    ownership.1 = Some(approved.clone());
+    token::save_ownership(env, token_id, &ownership);
 
     events::emit_approval(env, owner.clone(), approved.clone(), token_id);
     Ok(())
 }
 
+/// Approves `approved` for every token in `token_ids`; all-or-nothing.
+pub fn batch_approve(
+    env: &Env,
+    owner: &Address,
+    approved: &Address,
+    token_ids: Vec<u64>,
+) -> Result<(), ContractError> {
+    let n = token_ids.len();
+    if n == 0 || n > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge);
+    }
+    for token_id in token_ids.iter() {
+        approve(env, owner, approved, token_id)?;
+    }
+    events::emit_batch_approval(env, owner.clone(), approved.clone(), token_ids);
+    Ok(())
+}
+
 pub fn set_approval_for_all(env: &Env, owner: &Address, operator: &Address, approved: bool) {
     env.storage().persistent().set(
         &DataKey::OperatorApproval(owner.clone(), operator.clone()),
@@ -36,9 +57,7 @@ pub fn set_approval_for_all(env: &Env, owner: &Address, operator: &Address, appr
 }
 
 pub fn get_approved(env: &Env, token_id: u64) -> Option<Address> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::TokenApproved(token_id))
+    token::load_ownership(env, token_id).and_then(|o| o.1)
 }
 
 pub fn is_approved_for_all(env: &Env, owner: &Address, operator: &Address) -> bool {
@@ -50,31 +69,38 @@ pub fn is_approved_for_all(env: &Env, owner: &Address, operator: &Address) -> bo
 
 /// Returns true if `spender` is the owner, approved for the token, or an operator.
 pub fn is_approved_or_owner(env: &Env, spender: &Address, token_id: u64) -> bool {
-    let owner: Address = match env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenOwner(token_id))
-    {
+    let ownership = match token::load_ownership(env, token_id) {
         Some(o) => o,
         None => return false,
     };
 
-    if spender == &owner {
+    if spender == ownership.owner() {
         return true;
     }
-    if let Some(approved) = env
-        .storage()
-        .persistent()
-        .get::<_, Address>(&DataKey::TokenApproved(token_id))
-        && spender == &approved
-    {
+    if ownership.approved() == Some(spender) {
         return true;
     }
-    is_approved_for_all(env, &owner, spender)
+    is_approved_for_all(env, ownership.owner(), spender)
+}
+
+/// Checks that `caller` may move `token_id`. A locker moving a token it holds
+/// the lock on settles (releases) the lock instead of needing an approval.
+pub fn authorize_transfer(env: &Env, caller: &Address, token_id: u64) -> Result<(), ContractError> {
+    if let Some(token_lock) = lock::get_lock(env, token_id)
+        && caller == &token_lock.locker
+    {
+        lock::release(env, token_id, token_lock);
+        return Ok(());
+    }
+    if !is_approved_or_owner(env, caller, token_id) {
+        return Err(ContractError::NotApproved);
+    }
+    Ok(())
 }
 
 pub fn do_transfer(
     env: &Env,
+    operator: &Address,
     from: &Address,
     to: &Address,
     token_id: u64,
@@ -88,43 +114,32 @@ pub fn do_transfer(
         return Err(ContractError::ContractPaused);
     }
 
-    let owner: Address = env
-        .storage()
-        .persistent()
-        .get(&DataKey::TokenOwner(token_id))
-        .ok_or(ContractError::TokenNotFound)?;
+    let ownership = token::load_ownership(env, token_id).ok_or(ContractError::TokenNotFound)?;
 
-    if &owner != from {
+    if ownership.owner() != from {
         return Err(ContractError::NotOwner);
     }
+    if lock::is_locked(env, token_id) {
+        return Err(ContractError::TokenLocked);
+    }
+    let rules = transfer_rules::get_rules(env);
+    transfer_rules::check_transfer(env, &rules, Some(from), to, token_id)?;
 
-    // Clear per-token approval on transfer
-    env.storage()
-        .persistent()
-        .remove(&DataKey::TokenApproved(token_id));
-
-    env.storage()
-        .persistent()
-        .set(&DataKey::TokenOwner(token_id), to);
+    // New owner with the per-token approval cleared, in a single write
+    token::save_ownership(env, token_id, &TokenOwnership(to.clone(), None));
 
     // Update balances
-    let from_bal: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Balance(from.clone()))
-        .unwrap_or(0);
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(from.clone()), &from_bal.saturating_sub(1));
-
-    let to_bal: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Balance(to.clone()))
-        .unwrap_or(0);
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balance(to.clone()), &(to_bal + 1));
+    token::decrement_balance(env, from);
+    token::increment_balance(env, to);
+    transfer_rules::record_receipt(env, &rules, token_id);
+    provenance::record(
+        env,
+        token_id,
+        ProvenanceAction::Transfer,
+        Some(from.clone()),
+        Some(to.clone()),
+        operator,
+    );
 
     events::emit_transfer(env, from.clone(), to.clone(), token_id);
     Ok(())
@@ -0,0 +1,104 @@
+use crate::access_control;
+use crate::error::ContractError;
+use crate::events;
+use crate::storage::DataKey;
+use crate::token;
+use crate::types::TransferRules;
+use soroban_sdk::{Address, Env};
+
+pub fn get_rules(env: &Env) -> TransferRules {
+    env.storage()
+        .instance()
+        .get(&DataKey::TransferRules)
+        .unwrap_or(TransferRules {
+            max_per_wallet: None,
+            cooldown: 0,
+        })
+}
+
+pub fn set_rules(
+    env: &Env,
+    caller: &Address,
+    max_per_wallet: Option<u64>,
+    cooldown: u64,
+) -> Result<(), ContractError> {
+    access_control::require_admin_or_owner(env, caller);
+    if max_per_wallet == Some(0) {
+        return Err(ContractError::InvalidAmount);
+    }
+    env.storage().instance().set(
+        &DataKey::TransferRules,
+        &TransferRules {
+            max_per_wallet,
+            cooldown,
+        },
+    );
+    events::emit_transfer_rules_update(env, max_per_wallet, cooldown);
+    Ok(())
+}
+
+/// Exempts `address` (e.g. marketplace escrow or the collection owner) from
+/// both the wallet cap and the holding period.
+pub fn set_exemption(env: &Env, caller: &Address, address: &Address, exempt: bool) {
+    access_control::require_admin_or_owner(env, caller);
+    if exempt {
+        env.storage()
+            .instance()
+            .set(&DataKey::RuleExempt(address.clone()), &true);
+    } else {
+        env.storage()
+            .instance()
+            .remove(&DataKey::RuleExempt(address.clone()));
+    }
+    events::emit_rule_exemption_update(env, address.clone(), exempt);
+}
+
+/// The contract's own claim escrow is always exempt.
+pub fn is_exempt(env: &Env, address: &Address) -> bool {
+    address == &env.current_contract_address()
+        || env
+            .storage()
+            .instance()
+            .get(&DataKey::RuleExempt(address.clone()))
+            .unwrap_or(false)
+}
+
+/// Rejects the transfer if `to` would exceed the wallet cap or `token_id`
+/// is still inside `from`'s holding period.
+pub fn check_transfer(
+    env: &Env,
+    rules: &TransferRules,
+    from: Option<&Address>,
+    to: &Address,
+    token_id: u64,
+) -> Result<(), ContractError> {
+    if rules.cooldown > 0
+        && let Some(from) = from
+        && !is_exempt(env, from)
+    {
+        let held_since: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::HeldSince(token_id))
+            .unwrap_or(0);
+        if env.ledger().timestamp() < held_since.saturating_add(rules.cooldown) {
+            return Err(ContractError::TransferCooldown);
+        }
+    }
+    if let Some(max) = rules.max_per_wallet
+        && !is_exempt(env, to)
+        && token::balance_of(env, to) >= max
+    {
+        return Err(ContractError::WalletLimitExceeded);
+    }
+    Ok(())
+}
+
+/// Starts the holding period for the token's new owner.
+pub fn record_receipt(env: &Env, rules: &TransferRules, token_id: u64) {
+    if rules.cooldown > 0 {
+        env.storage()
+            .persistent()
+            .set(&DataKey::HeldSince(token_id), &env.ledger().timestamp());
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, String, Vec, contracttype};
+use soroban_sdk::{Address, BytesN, String, Vec, contracttype};
 
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -8,6 +8,50 @@ pub struct TokenAttribute {
     pub display_type: Option<String>,
 }
 
+/// Hot per-token state `(owner, approved)`: the single source of truth for
+/// ownership, read and rewritten by every transfer. A tuple struct, so the
+/// entry carries no field names.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TokenOwnership(pub Address, pub Option<Address>);
+
+impl TokenOwnership {
+    pub fn owner(&self) -> &Address {
+        &self.0
+    }
+
+    pub fn approved(&self) -> Option<&Address> {
+        self.1.as_ref()
+    }
+}
+
+/// Cold per-token metadata, written at mint and on URI updates only.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TokenMetadata {
+    pub metadata_uri: String,
+    pub created_at: u64,
+    pub creator: Address,
+    pub royalty_percentage: u32,
+    pub royalty_recipient: Address,
+    pub attributes: Vec<TokenAttribute>,
+    pub edition_number: Option<u32>,
+    pub total_editions: Option<u32>,
+}
+
+/// Optional integrity and media fields. `content_hash` is the SHA-256 of the
+/// off-chain metadata JSON; `media_hash` that of the primary media file.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[contracttype]
+pub struct TokenMedia {
+    pub content_hash: Option<BytesN<32>>,
+    pub image_uri: Option<String>,
+    pub animation_url: Option<String>,
+    pub media_hash: Option<BytesN<32>>,
+}
+
+/// Full token view assembled from `TokenOwnership` and `TokenMetadata`.
+/// Also the pre-split storage layout read by `migrate_token_storage`.
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct TokenData {
@@ -42,6 +86,218 @@ pub struct CollectionConfig {
     pub metadata_is_frozen: bool,
 }
 
+/// A token parked in contract escrow until someone presents the preimage of `claim_hash`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct PendingClaim {
+    pub sender: Address,
+    pub claim_hash: BytesN<32>,
+    pub expires_at: u64,
+}
+
+/// An owner's recovery guardians. `threshold` approvals start a `delay`-second
+/// challenge window before tokens can be moved.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RecoveryConfig {
+    pub guardians: Vec<Address>,
+    pub threshold: u32,
+    pub delay: u64,
+}
+
+/// An open recovery request; `ready_at` is 0 until the threshold is reached.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RecoveryRequest {
+    pub new_owner: Address,
+    pub approvals: Vec<Address>,
+    pub ready_at: u64,
+}
+
+/// A published Merkle airdrop. Leaves are minted lazily by their recipients.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AirdropCampaign {
+    pub root: BytesN<32>,
+    pub creator: Address,
+    pub leaf_count: u32,
+    pub claimed_count: u32,
+    pub expires_at: u64,
+}
+
+/// One airdrop allocation. The tree leaf is `sha256(leaf.to_xdr())` and
+/// `attributes_hash` is `sha256(attributes.to_xdr())`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AirdropLeaf {
+    pub index: u32,
+    pub recipient: Address,
+    pub metadata_uri: String,
+    pub attributes_hash: BytesN<32>,
+}
+
+/// Inclusive token ID range that only `minters` may mint into via `mint_with_id`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ReservedRange {
+    pub start: u64,
+    pub end: u64,
+    pub minters: Vec<Address>,
+}
+
+/// Anti-whale and anti-flip limits applied to mints and transfers.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TransferRules {
+    pub max_per_wallet: Option<u64>,
+    pub cooldown: u64, // Seconds a received token must be held; 0 disables
+}
+
+/// A token frozen in place by a LOCKER (e.g. an escrow-free listing or loan).
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct TokenLock {
+    pub locker: Address,
+    pub locked_at: u64,
+    pub unlock_at: u64,
+    pub index: u32, // Position in the locker's token index
+}
+
+/// Value recorded at the end of `ledger`; see `checkpoints`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: u64,
+}
+
+/// Single-call collection summary returned by `collection_info`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct CollectionInfo {
+    pub config: CollectionConfig,
+    pub contract_uri: Option<String>,
+    pub total_supply: u64,
+    pub burned_count: u64,
+    pub holder_count: u64,
+    pub next_token_id: u64,
+    pub is_paused: bool,
+    pub metadata_frozen: bool,
+    pub royalty_recipient: Option<Address>,
+    pub royalty_percentage: u32, // Default royalty; 0 when unset
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub enum ProvenanceAction {
+    Mint,
+    Transfer,
+    Burn,
+}
+
+/// One chain-of-custody record; `from` is `None` for mints, `to` for burns.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct ProvenanceEntry {
+    pub action: ProvenanceAction,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub operator: Address,
+    pub timestamp: u64,
+}
+
+/// One requirement of a redemption recipe.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum RecipeInput {
+    Token(u64),                     // a specific token
+    Attribute(String, String, u32), // (trait_type, value, count)
+    Any(u32),                       // any `count` tokens of this collection
+}
+
+/// What a redemption delivers to the redeemer.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum RecipeOutput {
+    Mint(String, Vec<TokenAttribute>), // (metadata_uri, attributes) in this collection
+    MintExternal(Address, String, Vec<TokenAttribute>), // (collection, metadata_uri, attributes)
+    Receipt,                           // `Redeemed` event only, e.g. for physical merch
+}
+
+/// An admin-defined burn-to-redeem recipe; see `redemption::redeem`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Recipe {
+    pub creator: Address,
+    pub inputs: Vec<RecipeInput>,
+    pub output: RecipeOutput,
+    pub active: bool,
+    pub redeemed_count: u32,
+}
+
+/// M-of-N approval for critical admin functions, fixed at initialisation.
+/// `co_admins` are granted ADMIN alongside the initial admin, so the
+/// threshold is reachable from the start. `protected_actions` must list
+/// GRANT_ROLE, REVOKE_ROLE and UPGRADE; `initialize` rejects it otherwise.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MultisigConfig {
+    pub co_admins: Vec<Address>,
+    pub threshold: u32,
+    pub protected_actions: Vec<u32>, // `admin_action` discriminants
+    pub proposal_ttl: u64,           // seconds a proposal stays open
+}
+
+/// A critical admin call awaiting approval; see `admin_action`.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub enum AdminCall {
+    SetPause(bool),
+    SetDefaultRoyalty(Address, u32),    // (recipient, percentage)
+    SetTokenRoyalty(u64, Address, u32), // (token_id, recipient, percentage)
+    SetBaseUri(String),
+    FreezeMetadata,
+    GrantRole(Address, u32),
+    RevokeRole(Address, u32),
+    Upgrade(BytesN<32>), // new wasm hash
+}
+
+impl AdminCall {
+    pub fn action(&self) -> u32 {
+        match self {
+            AdminCall::SetPause(_) => admin_action::SET_PAUSE,
+            AdminCall::SetDefaultRoyalty(..) => admin_action::SET_DEFAULT_ROYALTY,
+            AdminCall::SetTokenRoyalty(..) => admin_action::SET_TOKEN_ROYALTY,
+            AdminCall::SetBaseUri(_) => admin_action::SET_BASE_URI,
+            AdminCall::FreezeMetadata => admin_action::FREEZE_METADATA,
+            AdminCall::GrantRole(..) => admin_action::GRANT_ROLE,
+            AdminCall::RevokeRole(..) => admin_action::REVOKE_ROLE,
+            AdminCall::Upgrade(_) => admin_action::UPGRADE,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct AdminProposal {
+    pub call: AdminCall,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub expires_at: u64,
+}
+
+// Admin function discriminants listed in MultisigConfig::protected_actions
+pub mod admin_action {
+    pub const SET_PAUSE: u32 = 0;
+    pub const SET_DEFAULT_ROYALTY: u32 = 1;
+    pub const SET_TOKEN_ROYALTY: u32 = 2;
+    pub const SET_BASE_URI: u32 = 3;
+    pub const FREEZE_METADATA: u32 = 4;
+    pub const GRANT_ROLE: u32 = 5;
+    pub const REVOKE_ROLE: u32 = 6;
+    pub const UPGRADE: u32 = 7;
+}
+
 // Role discriminants stored in DataKey::Role(addr, discriminant)
 pub mod role {
     pub const OWNER: u32 = 0;
@@ -49,4 +305,5 @@ pub mod role {
     pub const MINTER: u32 = 2;
     pub const BURNER: u32 = 3;
     pub const METADATA_UPDATER: u32 = 4;
+    pub const LOCKER: u32 = 5;
 }